@@ -51,6 +51,10 @@ fn main() {
     println!("Generated TypeScript file:");
     println!("{content}");
 
+    // The same schemas as plain TypeScript declarations, without Zod
+    println!("Generated declarations:");
+    println!("{}", generator.generate_declarations());
+
     println!("=== Key Features Demonstrated ===");
     println!("✅ Structs with various field types (User, UserProfile)");
     println!("✅ Enums with unit variants (UserStatus)");
@@ -59,4 +63,5 @@ fn main() {
     println!("✅ User-controlled naming for TypeScript exports");
     println!("✅ Single file output with all schemas");
     println!("✅ TypeScript types inferred automatically with z.infer<>");
+    println!("✅ Plain TypeScript declarations without a Zod dependency");
 }
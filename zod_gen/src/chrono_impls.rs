@@ -0,0 +1,49 @@
+//! `ZodSchema` impls for chrono's date and time types.
+//!
+//! Chrono serializes all of them as ISO 8601 strings.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+use crate::{zod_string, ZodSchema, ZodType};
+
+macro_rules! impl_string_schema {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ZodSchema for $ty {
+                fn zod_schema() -> String {
+                    zod_string().to_string()
+                }
+
+                fn zod_type() -> ZodType {
+                    ZodType::String
+                }
+            }
+        )*
+    };
+}
+
+impl_string_schema!(NaiveDate, NaiveTime, NaiveDateTime);
+
+impl<Tz: TimeZone> ZodSchema for DateTime<Tz> {
+    fn zod_schema() -> String {
+        zod_string().to_string()
+    }
+
+    fn zod_type() -> ZodType {
+        ZodType::String
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    #[test]
+    fn test_chrono_types_are_strings() {
+        assert_eq!(DateTime::<Utc>::zod_schema(), "z.string()");
+        assert_eq!(NaiveDate::zod_type(), ZodType::String);
+        assert_eq!(NaiveDateTime::zod_type(), ZodType::String);
+    }
+}
@@ -0,0 +1,280 @@
+//! JSON Schema (draft 2020-12) rendered from [`ZodType`].
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::{ZodEnumVariant, ZodType};
+
+/// The `$schema` dialect of every generated document.
+pub(crate) const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Where `$ref`s to registered types point in a standalone JSON Schema.
+pub(crate) const DEFS_PREFIX: &str = "#/$defs/";
+
+/// Resolves references to registered types while rendering.
+pub(crate) struct Refs<'a> {
+    /// Maps the id of a [`ZodType::Named`] type to its export name.
+    pub names: &'a BTreeMap<String, String>,
+    /// Prefix of the `$ref` pointer, e.g. [`DEFS_PREFIX`].
+    pub prefix: &'a str,
+}
+
+/// Renders `schema` as a JSON Schema.
+///
+/// Registered types become a `$ref`, everything else is inlined.
+pub(crate) fn render_schema(schema: &ZodType, refs: &Refs<'_>) -> Value {
+    match schema {
+        ZodType::String => json!({ "type": "string" }),
+        ZodType::Number => json!({ "type": "number" }),
+        ZodType::BigInt => json!({ "type": "integer" }),
+        ZodType::Boolean => json!({ "type": "boolean" }),
+        ZodType::Null => json!({ "type": "null" }),
+        // Hand-written Zod expressions carry no structure we could translate,
+        // so they accept anything, like `z.any()`.
+        ZodType::Any | ZodType::Raw(_) => json!({}),
+        ZodType::Literal(value) => json!({ "const": value }),
+        ZodType::StringExcept(values) => json!({ "type": "string", "not": { "enum": values } }),
+        ZodType::Enum(variants) => {
+            let values: Vec<&str> = variants.iter().flat_map(ZodEnumVariant::values).collect();
+            json!({ "type": "string", "enum": values })
+        }
+        ZodType::NumericEnum(variants) => {
+            let values: Vec<i64> = variants.iter().map(|v| v.value).collect();
+            json!({ "type": "integer", "enum": values })
+        }
+        ZodType::Nullable(inner) => json!({
+            "anyOf": [render_schema(inner, refs), { "type": "null" }]
+        }),
+        // Optionality only matters for object properties, see below.
+        ZodType::Optional(inner) => render_schema(inner, refs),
+        ZodType::Array(inner) => json!({
+            "type": "array",
+            "items": render_schema(inner, refs),
+        }),
+        ZodType::Record(value) => json!({
+            "type": "object",
+            "additionalProperties": render_schema(value, refs),
+        }),
+        ZodType::Object(fields) => {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            // A required field with aliases may be given under any of its keys
+            let mut any_required = Vec::new();
+            for field in fields {
+                let keys = std::iter::once(&field.name).chain(&field.aliases);
+                if matches!(field.schema, ZodType::Optional(_) | ZodType::Default(..)) {
+                    // Missing is fine under every key
+                } else if field.aliases.is_empty() {
+                    required.push(Value::String(field.name.clone()));
+                } else {
+                    let alternatives: Vec<Value> = keys
+                        .clone()
+                        .map(|key| json!({ "required": [key] }))
+                        .collect();
+                    any_required.push(json!({ "anyOf": alternatives }));
+                }
+                let schema = render_schema(&field.schema, refs);
+                for key in keys {
+                    properties.insert(key.clone(), schema.clone());
+                }
+            }
+            let mut rendered = json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            });
+            if !any_required.is_empty() {
+                rendered["allOf"] = Value::Array(any_required);
+            }
+            rendered
+        }
+        ZodType::Default(inner, value) => {
+            let mut rendered = render_schema(inner, refs);
+            if let Some(map) = rendered.as_object_mut() {
+                map.insert("default".to_string(), value.clone());
+            }
+            rendered
+        }
+        // The metadata keys are JSON Schema keywords
+        ZodType::Meta(inner, meta) => {
+            let mut rendered = render_schema(inner, refs);
+            if let Some(map) = rendered.as_object_mut() {
+                map.extend(meta.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+            rendered
+        }
+        // Brands only exist in TypeScript's type system
+        ZodType::Brand(inner, _) => render_schema(inner, refs),
+        ZodType::Catchall(object, rest) => {
+            // The object is inlined, `additionalProperties` has to sit next
+            // to its `properties`.
+            let mut rendered = render_schema(object.shape(), refs);
+            match rendered.as_object_mut() {
+                Some(map) if map.contains_key("properties") => {
+                    map.insert(
+                        "additionalProperties".to_string(),
+                        render_schema(rest, refs),
+                    );
+                    rendered
+                }
+                // Without known properties, other keys can't be told apart
+                _ => rendered,
+            }
+        }
+        ZodType::Tuple(items) => {
+            let items: Vec<Value> = items.iter().map(|i| render_schema(i, refs)).collect();
+            json!({
+                "type": "array",
+                "minItems": items.len(),
+                "prefixItems": items,
+                "items": false,
+            })
+        }
+        ZodType::Union(variants) => json!({ "anyOf": render_all(variants, refs) }),
+        ZodType::DiscriminatedUnion { variants, .. } => {
+            json!({ "oneOf": render_all(variants, refs) })
+        }
+        ZodType::Intersection(left, right) => json!({
+            "allOf": [render_schema(left, refs), render_schema(right, refs)]
+        }),
+        ZodType::Named { id, schema, .. } => match refs.names.get(id) {
+            Some(export_name) => json!({ "$ref": format!("{}{export_name}", refs.prefix) }),
+            None => render_schema(schema, refs),
+        },
+    }
+}
+
+/// Renders the `$defs` entry for a registered schema.
+pub(crate) fn render_definition(schema: &ZodType, refs: &Refs<'_>) -> Value {
+    render_schema(schema.unnamed(), refs)
+}
+
+fn render_all(items: &[ZodType], refs: &Refs<'_>) -> Vec<Value> {
+    items.iter().map(|i| render_schema(i, refs)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ZodField;
+
+    fn render(schema: &ZodType) -> Value {
+        let names = BTreeMap::new();
+        render_schema(
+            schema,
+            &Refs {
+                names: &names,
+                prefix: DEFS_PREFIX,
+            },
+        )
+    }
+
+    #[test]
+    fn test_render_object() {
+        let schema = ZodType::object(vec![
+            ZodField::new("id", ZodType::Number),
+            ZodField::new("email", ZodType::nullable(ZodType::String)),
+            ZodField::new("nickname", ZodType::optional(ZodType::String)),
+        ]);
+        assert_eq!(
+            render(&schema),
+            json!({
+                "type": "object",
+                "properties": {
+                    "id": { "type": "number" },
+                    "email": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+                    "nickname": { "type": "string" },
+                },
+                "required": ["id", "email"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_aliased_object() {
+        let schema = ZodType::object(vec![
+            ZodField::new("email", ZodType::String).with_aliases(&["mail"])
+        ]);
+        assert_eq!(
+            render(&schema),
+            json!({
+                "type": "object",
+                "properties": {
+                    "email": { "type": "string" },
+                    "mail": { "type": "string" },
+                },
+                "required": [],
+                "allOf": [{ "anyOf": [{ "required": ["email"] }, { "required": ["mail"] }] }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_tuple() {
+        let schema = ZodType::Tuple(vec![ZodType::Number, ZodType::String]);
+        assert_eq!(
+            render(&schema),
+            json!({
+                "type": "array",
+                "minItems": 2,
+                "prefixItems": [{ "type": "number" }, { "type": "string" }],
+                "items": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_discriminated_union() {
+        let schema = ZodType::discriminated_union(
+            "type",
+            vec![ZodType::object(vec![ZodField::new(
+                "type",
+                ZodType::literal("A"),
+            )])],
+        );
+        assert_eq!(
+            render(&schema),
+            json!({
+                "oneOf": [{
+                    "type": "object",
+                    "properties": { "type": { "const": "A" } },
+                    "required": ["type"],
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_named_reference() {
+        let address = ZodType::named("app::Address", "Address", ZodType::String);
+        assert_eq!(render(&address), json!({ "type": "string" }));
+
+        let mut names = BTreeMap::new();
+        names.insert("app::Address".to_string(), "Address".to_string());
+        let refs = Refs {
+            names: &names,
+            prefix: "#/components/schemas/",
+        };
+        assert_eq!(
+            render_schema(&address, &refs),
+            json!({ "$ref": "#/components/schemas/Address" })
+        );
+        assert_eq!(
+            render_definition(&address, &refs),
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn test_render_meta() {
+        let mut meta = serde_json::Map::new();
+        meta.insert("title".to_string(), json!("Amount"));
+        meta.insert("examples".to_string(), json!([5]));
+        let schema = ZodType::meta(ZodType::Number, meta);
+        assert_eq!(
+            render(&schema),
+            json!({ "type": "number", "title": "Amount", "examples": [5] })
+        );
+    }
+}
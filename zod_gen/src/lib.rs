@@ -45,7 +45,7 @@
 //! });
 //! export type User = z.infer<typeof UserSchema>;
 //!
-//! export const StatusSchema = z.union([z.literal("active"), z.literal("inactive")]);
+//! export const StatusSchema = z.enum(['active', 'inactive']);
 //! export type Status = z.infer<typeof StatusSchema>;
 //! ```
 //!
@@ -75,8 +75,26 @@
 //! - **Type Safety**: Generated TypeScript types match your Rust types exactly
 //! - **Generic Types**: Built-in support for `Option<T>`, `Vec<T>`, `HashMap<String, T>`
 //! - **Batch Generation**: Generate multiple schemas in a single TypeScript file
+//! - **Plain TypeScript**: Emit standalone `.d.ts` declarations without Zod via
+//!   [`ZodGenerator::generate_declarations`]
+//! - **JSON Schema**: Export a draft 2020-12 document with `$defs` via
+//!   [`ZodGenerator::generate_json_schema`]
+//! - **OpenAPI 3.1**: Render `components.schemas` and declared operations as a
+//!   complete spec via the [`openapi`] module (YAML output behind the `yaml` feature)
+//! - **Change Detection**: [`ZodGenerator::write_to`] only touches files whose
+//!   content changed, [`ZodGenerator::check`] reports stale files with a diff in CI
+//! - **Multi-File Output**: Split schemas into per-module files with cross-file
+//!   imports and an `index.ts` barrel via [`ZodGenerator::generate_modules`]
+//! - **Automatic Registration**: `#[zod(export)]` types are collected at link
+//!   time by [`ZodGenerator::from_registry`]
+//! - **Validation**: Check JSON against a schema in Rust, with Zod's issues,
+//!   via [`ZodType::validate`], and assert in tests that serde's output
+//!   conforms to the schema with [`testing::assert_conforms`]
+//! - **Chrono**: `DateTime`, `NaiveDate`, `NaiveTime` and `NaiveDateTime` map to
+//!   `z.string()` with the `chrono` feature
 //!
-//! Note: Internally tagged newtype variants that wrap structs are flattened via `z.intersection(...)`.
+//! Note: Internally tagged newtype variants that wrap structs are flattened into a single
+//! object with the tag field, see [`ZodObjectSchema`].
 //! Tuple variants are rejected for internally tagged enums to match Serde's rules.
 //!
 //! ## Installation
@@ -90,17 +108,294 @@
 //! serde = { version = "1.0", features = ["derive"] }
 //! ```
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+#[cfg(feature = "chrono")]
+mod chrono_impls;
+mod json_schema;
+mod modules;
+pub mod openapi;
+mod output;
+pub mod registry;
+mod schema;
+pub mod testing;
+mod typescript;
+mod validate;
+
+pub use modules::ModuleOptions;
+pub use output::{CheckError, DiffLine};
+use schema::jsdoc_deprecated;
+pub use schema::{ZodEnumVariant, ZodField, ZodNumericEnumVariant, ZodType};
+pub use validate::{PathSegment, ZodIssue, ZodIssueCode};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use inventory;
+    pub use serde_json;
+
+    use crate::{ZodField, ZodObjectSchema, ZodSchema, ZodType};
+
+    // The derive calls the same function for both sides, so that a missing
+    // impl is reported once.
+
+    /// Called by the derive for every field, spanned on the field's type, so
+    /// that a missing impl is reported there rather than in generated code.
+    pub fn field_schema<T: ZodSchema>(input: bool) -> ZodType {
+        if input {
+            T::zod_input_type()
+        } else {
+            T::zod_type()
+        }
+    }
+
+    /// Schema of an internally tagged newtype variant: the payload's fields
+    /// with the tag field in front, as serde serializes them.
+    pub fn internally_tagged<T: ZodObjectSchema>(tag: ZodField, input: bool) -> ZodType {
+        let mut fields = vec![tag];
+        fields.extend(flattened_fields::<T>(input));
+        if input {
+            object(fields, T::input_catchall())
+        } else {
+            object(fields, T::catchall())
+        }
+    }
+
+    /// Fields of a `#[serde(flatten)]` field, spanned on its type.
+    pub fn flattened_fields<T: ZodObjectSchema>(input: bool) -> Vec<ZodField> {
+        if input {
+            T::input_object_fields()
+        } else {
+            T::object_fields()
+        }
+    }
+
+    /// A function returning the value serde fills in for a missing field.
+    pub struct DefaultValue<T>(pub fn() -> T);
+
+    // Method resolution picks `SerializeDefault` when the value can be
+    // serialized, and falls back to `NoDefault` otherwise.
+
+    pub trait SerializeDefault {
+        fn default_json(&self) -> Option<serde_json::Value>;
+    }
+
+    impl<T: serde::Serialize> SerializeDefault for DefaultValue<T> {
+        fn default_json(&self) -> Option<serde_json::Value> {
+            serde_json::to_value((self.0)()).ok()
+        }
+    }
+
+    pub trait NoDefault {
+        fn default_json(&self) -> Option<serde_json::Value> {
+            None
+        }
+    }
+
+    impl<T> NoDefault for &DefaultValue<T> {}
+
+    /// Schema of a field that may be missing from the input: filled in with
+    /// `value` if known, otherwise just optional.
+    pub fn with_default(schema: ZodType, value: Option<serde_json::Value>) -> ZodType {
+        match value {
+            Some(value) => ZodType::with_default(schema, value),
+            None => ZodType::optional(schema),
+        }
+    }
+
+    /// Attaches `#[zod(...)]` metadata like a title or examples.
+    pub fn meta(schema: ZodType, entries: Vec<(&str, serde_json::Value)>) -> ZodType {
+        let meta = entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect();
+        ZodType::meta(schema, meta)
+    }
+
+    /// An object schema, with a catchall if any flattened field has one.
+    pub fn object(fields: Vec<ZodField>, catchall: Option<ZodType>) -> ZodType {
+        match catchall {
+            None => ZodType::object(fields),
+            Some(rest) if fields.is_empty() => ZodType::record(rest),
+            Some(rest) => ZodType::catchall(ZodType::object(fields), rest),
+        }
+    }
+}
 
 /// Trait for Rust types that can produce a Zod schema
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not implement `ZodSchema`",
+    label = "no Zod schema for this type",
+    note = "derive or implement `ZodSchema` for `{Self}`",
+    note = "or describe the field with another type's schema: `#[zod(as = OtherType)]`",
+    note = "for types from other crates, enable the matching zod_gen feature, e.g. `chrono`"
+)]
 pub trait ZodSchema {
     /// Returns the Zod schema as a string
     fn zod_schema() -> String;
+
+    /// Returns the structured schema this type produces.
+    ///
+    /// The default wraps [`zod_schema`](Self::zod_schema) in [`ZodType::Raw`],
+    /// so manual impls keep working unchanged. Raw schemas render as `unknown`
+    /// in plain TypeScript declarations; implement this method to give them a
+    /// proper type.
+    fn zod_type() -> ZodType {
+        ZodType::Raw(Self::zod_schema())
+    }
+
+    /// Returns the structured schema of the JSON this type accepts when
+    /// deserialized.
+    ///
+    /// [`zod_type`](Self::zod_type) describes the JSON a value serializes to.
+    /// Serde attributes like `default`, `skip_serializing_if` or
+    /// `rename(deserialize = "...")` make the two differ; the derive describes
+    /// both. Defaults to [`zod_type`](Self::zod_type).
+    fn zod_input_type() -> ZodType {
+        Self::zod_type()
+    }
+
+    /// Returns the Zod schema of [`zod_input_type`](Self::zod_input_type) as a
+    /// string.
+    fn zod_input_schema() -> String {
+        Self::zod_input_type().to_zod()
+    }
+
+    /// Returns the name this type is exported under when another registered
+    /// schema depends on it.
+    ///
+    /// `None` for anonymous types like `Vec<T>`, which are always inlined.
+    /// Derived types return their Rust type name, or the name given with
+    /// `#[zod(rename = "...")]`.
+    fn type_name() -> Option<String> {
+        match Self::zod_type() {
+            ZodType::Named { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the named types this type's schema refers to directly.
+    ///
+    /// Each entry is a [`ZodType::Named`]. [`ZodGenerator::add_schema`] walks
+    /// these to register every reachable named type.
+    fn dependencies() -> Vec<ZodType> {
+        Self::zod_type().dependencies()
+    }
 }
 
-/// Marker trait for types that produce an object-like Zod schema (z.object(...)).
-/// Used for internally tagged newtype variants to properly flatten payload fields.
-pub trait ZodObjectSchema: ZodSchema {}
+/// Trait for types whose schema is an object (`z.object(...)`), giving access
+/// to its fields.
+///
+/// The derive implements it for structs with named fields, and
+/// `HashMap<String, T>` implements it as an object with only "other" keys.
+/// Internally tagged newtype variants and `#[serde(flatten)]` fields require
+/// it, and merge the fields into the surrounding object.
+///
+/// ```rust
+/// use zod_gen::{ZodField, ZodObjectSchema, ZodSchema, ZodType};
+///
+/// struct User;
+///
+/// impl ZodSchema for User {
+///     fn zod_schema() -> String {
+///         Self::zod_type().to_zod()
+///     }
+///
+///     fn zod_type() -> ZodType {
+///         Self::object_type()
+///     }
+/// }
+///
+/// impl ZodObjectSchema for User {
+///     fn object_fields() -> Vec<ZodField> {
+///         vec![
+///             ZodField::new("id", ZodType::Number),
+///             ZodField::new("name", ZodType::String),
+///         ]
+///     }
+/// }
+///
+/// assert_eq!(User::pick(&["id"]).to_zod(), "z.object({\n  id: z.number()\n})");
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not produce an object schema",
+    label = "not an object schema",
+    note = "structs with named fields and maps produce object schemas",
+    note = "internally tagged newtype variants and #[serde(flatten)] fields must hold one, as serde requires"
+)]
+pub trait ZodObjectSchema: ZodSchema {
+    /// The object's known fields, in serialization order.
+    fn object_fields() -> Vec<ZodField>;
+
+    /// Schema of every other key, for maps and types flattening a map.
+    ///
+    /// `None` if the object has no other keys.
+    fn catchall() -> Option<ZodType> {
+        None
+    }
+
+    /// The object schema made of [`object_fields`](Self::object_fields) and
+    /// [`catchall`](Self::catchall).
+    fn object_type() -> ZodType {
+        __private::object(Self::object_fields(), Self::catchall())
+    }
+
+    /// The object's fields when deserializing, see
+    /// [`ZodSchema::zod_input_type`]. Defaults to
+    /// [`object_fields`](Self::object_fields).
+    fn input_object_fields() -> Vec<ZodField> {
+        Self::object_fields()
+    }
+
+    /// Schema of every other key when deserializing. Defaults to
+    /// [`catchall`](Self::catchall).
+    fn input_catchall() -> Option<ZodType> {
+        Self::catchall()
+    }
+
+    /// The input object schema made of
+    /// [`input_object_fields`](Self::input_object_fields) and
+    /// [`input_catchall`](Self::input_catchall).
+    fn input_object_type() -> ZodType {
+        __private::object(Self::input_object_fields(), Self::input_catchall())
+    }
+
+    /// Like Zod's `.extend()`: this object with `fields` added, replacing
+    /// existing fields of the same name.
+    fn extend(fields: Vec<ZodField>) -> ZodType {
+        let mut merged = Self::object_fields();
+        for field in fields {
+            match merged.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => *existing = field,
+                None => merged.push(field),
+            }
+        }
+        __private::object(merged, Self::catchall())
+    }
+
+    /// Like Zod's `.pick()`: an object with only the named fields, in the
+    /// order of this object. Other keys are not allowed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a name is not a field of this object.
+    fn pick(names: &[&str]) -> ZodType {
+        let fields = Self::object_fields();
+        for name in names {
+            assert!(
+                fields.iter().any(|f| f.name == *name),
+                "`{}` has no field `{name}`",
+                std::any::type_name::<Self>()
+            );
+        }
+        ZodType::object(
+            fields
+                .into_iter()
+                .filter(|f| names.contains(&f.name.as_str()))
+                .collect(),
+        )
+    }
+}
 
 /// Helper functions for building Zod schema strings
 pub fn zod_string() -> &'static str {
@@ -119,6 +414,9 @@ pub fn zod_boolean() -> &'static str {
 pub fn zod_nullable(inner: &str) -> String {
     format!("{inner}.nullable()")
 }
+pub fn zod_optional(inner: &str) -> String {
+    format!("{inner}.optional()")
+}
 pub fn zod_array(inner: &str) -> String {
     format!("z.array({inner})")
 }
@@ -131,6 +429,35 @@ pub fn zod_object(fields: &[(&str, &str)]) -> String {
     format!("z.object({{\n{}\n}})", items.join(",\n"))
 }
 
+pub fn zod_catchall(object: &str, rest: &str) -> String {
+    format!("{object}.catchall({rest})")
+}
+
+/// Renders a string schema refined to reject `values`, e.g.
+/// `z.string().refine((value) => !['a', 'b'].includes(value))`.
+pub fn zod_string_except(values: &[&str]) -> String {
+    let values: Vec<String> = values.iter().map(|v| format!("'{v}'")).collect();
+    format!(
+        "z.string().refine((value) => ![{}].includes(value))",
+        values.join(", ")
+    )
+}
+
+/// Renders `<schema>.default(<value>)`, with `value` written as a JSON literal.
+pub fn zod_default(schema: &str, value: &serde_json::Value) -> String {
+    format!("{schema}.default({value})")
+}
+
+/// Renders `<schema>.meta({...})`, with `meta` written as a JSON object.
+pub fn zod_meta(schema: &str, meta: &serde_json::Map<String, serde_json::Value>) -> String {
+    format!("{schema}.meta({})", serde_json::Value::Object(meta.clone()))
+}
+
+/// Renders `<schema>.brand<"<brand>">()`.
+pub fn zod_brand(schema: &str, brand: &str) -> String {
+    format!("{schema}.brand<\"{brand}\">()")
+}
+
 pub fn zod_literal(value: &str) -> String {
     format!("z.literal('{value}')")
 }
@@ -159,21 +486,149 @@ pub fn zod_intersection(a: &str, b: &str) -> String {
 }
 
 pub fn zod_enum(variants: &[&str]) -> String {
-    let lits: Vec<String> = variants
-        .iter()
-        .map(|v| format!("z.literal('{v}')"))
-        .collect();
-    format!("z.union([{}])", lits.join(", "))
+    let values: Vec<String> = variants.iter().map(|v| format!("'{v}'")).collect();
+    format!("z.enum([{}])", values.join(", "))
+}
+
+/// Renders a union of integer literals, e.g. `z.union([z.literal(0), z.literal(1)])`.
+///
+/// A single value renders as just its literal and no values as `z.never()`,
+/// since `z.union` needs at least two members.
+pub fn zod_numeric_enum(values: &[i64]) -> String {
+    match values {
+        [] => "z.never()".to_string(),
+        [value] => format!("z.literal({value})"),
+        _ => {
+            let literals: Vec<String> = values.iter().map(|v| format!("z.literal({v})")).collect();
+            format!("z.union([{}])", literals.join(", "))
+        }
+    }
+}
+
+/// Error returned when a schema cannot be registered with a [`ZodGenerator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistrationError {
+    /// Two different schemas would be exported under the same name.
+    DuplicateName {
+        name: String,
+        /// The schema already registered under `name`.
+        existing: String,
+        /// The schema that was about to be registered.
+        new: String,
+    },
+    /// [`ZodGenerator::add`] was called for a type without a
+    /// [`type_name`](ZodSchema::type_name).
+    MissingName { rust_type: &'static str },
+    /// [`ZodGenerator::generate_modules`] would put the schema exported as
+    /// `name` into the `index` module, which is the generated barrel file.
+    ReservedModule { name: String },
+}
+
+impl RegistrationError {
+    fn duplicate(name: &str, existing: &ZodType, new: &ZodType) -> Self {
+        let describe = |schema: &ZodType| match schema {
+            ZodType::Named { id, .. } => format!("`{id}`"),
+            other => format!("`{}`", other.to_zod()),
+        };
+        RegistrationError::DuplicateName {
+            name: name.to_string(),
+            existing: describe(existing),
+            new: describe(new),
+        }
+    }
+}
+
+impl std::fmt::Display for RegistrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistrationError::DuplicateName {
+                name,
+                existing,
+                new,
+            } => write!(
+                f,
+                "export name `{name}` is used by both {existing} and {new}; \
+                 give one of them a different name with #[zod(rename = \"...\")] \
+                 or by adding it explicitly with `add_schema`"
+            ),
+            RegistrationError::MissingName { rust_type } => write!(
+                f,
+                "`{rust_type}` has no export name; use `add_schema` to name it explicitly"
+            ),
+            RegistrationError::ReservedModule { name } => write!(
+                f,
+                "`{name}` would be generated into the `index` module, which is reserved \
+                 for the barrel file; move it to another module with `set_module`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistrationError {}
+
+/// Whether two schemas describe the same type, for duplicate detection.
+fn same_type(a: &ZodType, b: &ZodType) -> bool {
+    match (a, b) {
+        (ZodType::Named { id: a, .. }, ZodType::Named { id: b, .. }) => a == b,
+        _ => a == b,
+    }
+}
+
+/// A change made by a registration, with the state it replaced, so that a
+/// failed registration can be undone without copying the generator.
+enum Change {
+    /// The previous schema of a name on one side.
+    Schema(Side, String, Option<ZodType>),
+    /// Whether a name was registered as a dependency before.
+    DependencyName(String, bool),
+}
+
+/// Which way a registered schema describes JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    /// As serialized, see [`ZodSchema::zod_type`].
+    Output,
+    /// As accepted when deserializing, see [`ZodSchema::zod_input_type`].
+    Input,
+}
+
+/// A schema as it appears in the generated output.
+pub(crate) struct Export<'a> {
+    /// The name the schema was registered under.
+    pub(crate) registered: &'a str,
+    pub(crate) schema: &'a ZodType,
+    /// The side the schema describes, `None` if input and output are equal.
+    pub(crate) side: Option<Side>,
+}
+
+impl Export<'_> {
+    /// The side whose export names references in the schema resolve to.
+    pub(crate) fn names_side(&self) -> Side {
+        self.side.unwrap_or(Side::Output)
+    }
 }
 
 /// Generator that collects schemas and writes TypeScript files
 ///
 /// The `ZodGenerator` generates Zod schemas with proper serde rename support
 /// from Rust types, providing TypeScript type safety.
+#[derive(Debug)]
 pub struct ZodGenerator {
     // Use a btreemap so we retain key order which is useful to ensure the
     // output is stable (e.g. if zod_gen is run on CI)
-    schemas: BTreeMap<String, String>,
+    schemas: BTreeMap<String, ZodType>,
+    // Input schemas by export name. Dependencies that only appear on one side
+    // are only registered on that side.
+    input_schemas: BTreeMap<String, ZodType>,
+    // Export names of schemas that were registered as a dependency of another
+    // schema rather than added explicitly
+    dependency_names: BTreeSet<String>,
+    // Explicit output modules for multi-file generation, keyed by export name
+    modules: BTreeMap<String, String>,
+    // Whether `z.enum` schemas get an `export const X = { ... } as const`
+    enum_constants: bool,
+    // Whether exported schemas get their export name as `id` metadata
+    schema_ids: bool,
 }
 
 impl Default for ZodGenerator {
@@ -186,13 +641,263 @@ impl ZodGenerator {
     pub fn new() -> Self {
         Self {
             schemas: BTreeMap::new(),
+            input_schemas: BTreeMap::new(),
+            dependency_names: BTreeSet::new(),
+            modules: BTreeMap::new(),
+            enum_constants: false,
+            schema_ids: false,
+        }
+    }
+
+    /// Create a generator with every type exported via `#[zod(export)]`
+    ///
+    /// Types deriving `ZodSchema` with `#[zod(export)]` register themselves
+    /// under their export name at link time, in every crate linked into the
+    /// binary. Crates that are not referenced at all may be dropped by the
+    /// linker; `use` something from them to make sure their types are found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two different exported types share an export name; use
+    /// [`try_from_registry`](Self::try_from_registry) to handle that instead.
+    pub fn from_registry() -> Self {
+        Self::try_from_registry().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Like [`from_registry`](Self::from_registry), but reports duplicate
+    /// export names as an error.
+    pub fn try_from_registry() -> Result<Self, RegistrationError> {
+        let mut generator = Self::new();
+        for exported in registry::exported_schemas() {
+            generator.insert(
+                exported.name().to_string(),
+                exported.schema(),
+                exported.input_schema(),
+                &mut Vec::new(),
+            )?;
         }
+        Ok(generator)
+    }
+
+    /// Add a Zod schema for a Rust type under its own export name
+    ///
+    /// Derived types are exported under their Rust type name, or the name
+    /// given with `#[zod(rename = "...")]`. Fails for types without a
+    /// [`type_name`](ZodSchema::type_name), like `Vec<T>`, and when the name
+    /// is already used by a different schema.
+    pub fn add<T: ZodSchema>(&mut self) -> Result<&mut Self, RegistrationError> {
+        let name = T::type_name().ok_or(RegistrationError::MissingName {
+            rust_type: std::any::type_name::<T>(),
+        })?;
+        self.try_add_schema::<T>(&name)
     }
 
     /// Add a Zod schema for a Rust type
+    ///
+    /// Every named type the schema depends on, directly or transitively, is
+    /// registered as well under its [`type_name`](ZodSchema::type_name),
+    /// unless the same type is already registered. Adding such a dependency
+    /// explicitly later replaces the automatic entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name`, or the name of a dependency, is already used by a
+    /// different schema; use [`try_add_schema`](Self::try_add_schema) to handle
+    /// that instead.
     pub fn add_schema<T: ZodSchema>(&mut self, name: &str) {
-        let schema = T::zod_schema();
-        self.schemas.insert(name.to_string(), schema);
+        if let Err(err) = self.try_add_schema::<T>(name) {
+            panic!("{err}");
+        }
+    }
+
+    /// Like [`add_schema`](Self::add_schema), but reports duplicate export
+    /// names as an error. The generator is left unchanged on error.
+    pub fn try_add_schema<T: ZodSchema>(
+        &mut self,
+        name: &str,
+    ) -> Result<&mut Self, RegistrationError> {
+        let mut changes = Vec::new();
+        if let Err(err) = self.insert(
+            name.to_string(),
+            T::zod_type(),
+            T::zod_input_type(),
+            &mut changes,
+        ) {
+            self.undo(changes);
+            return Err(err);
+        }
+        Ok(self)
+    }
+
+    /// Registers an explicitly added schema and its dependencies, recording
+    /// each change in `changes`.
+    fn insert(
+        &mut self,
+        name: String,
+        schema: ZodType,
+        input: ZodType,
+        changes: &mut Vec<Change>,
+    ) -> Result<(), RegistrationError> {
+        if let Some(existing) = self.registered(&name) {
+            if !same_type(existing, &schema) {
+                return Err(RegistrationError::duplicate(&name, existing, &schema));
+            }
+        }
+        if let ZodType::Named { id, .. } = &schema {
+            // An explicit registration replaces the automatic one of the same type
+            let automatic = self
+                .dependency_names
+                .iter()
+                .find(|n| matches!(self.registered(n), Some(ZodType::Named { id: other, .. }) if other == id))
+                .cloned();
+            if let Some(automatic) = automatic {
+                self.set_dependency_name(&automatic, false, changes);
+                self.set_schema(Side::Output, &automatic, None, changes);
+                self.set_schema(Side::Input, &automatic, None, changes);
+            }
+        }
+        self.set_dependency_name(&name, false, changes);
+        self.set_schema(Side::Output, &name, Some(schema.clone()), changes);
+        self.set_schema(Side::Input, &name, Some(input.clone()), changes);
+        self.insert_dependencies(&schema, Side::Output, changes)?;
+        self.insert_dependencies(&input, Side::Input, changes)
+    }
+
+    /// Registers every named type reachable from `schema` that is not
+    /// registered on `side` yet.
+    fn insert_dependencies(
+        &mut self,
+        schema: &ZodType,
+        side: Side,
+        changes: &mut Vec<Change>,
+    ) -> Result<(), RegistrationError> {
+        for dependency in schema.dependencies() {
+            let ZodType::Named { id, name, .. } = &dependency else {
+                continue;
+            };
+            if self.is_registered(id, side) {
+                continue;
+            }
+            // The other side of the same type may already be registered
+            if let Some(existing) = self.registered(name) {
+                if !same_type(existing, &dependency) {
+                    return Err(RegistrationError::duplicate(name, existing, &dependency));
+                }
+            }
+            self.set_dependency_name(name, true, changes);
+            self.set_schema(side, name, Some(dependency.clone()), changes);
+            self.insert_dependencies(&dependency, side, changes)?;
+        }
+        Ok(())
+    }
+
+    /// Sets or removes the schema of `name` on `side`.
+    fn set_schema(
+        &mut self,
+        side: Side,
+        name: &str,
+        schema: Option<ZodType>,
+        changes: &mut Vec<Change>,
+    ) {
+        let schemas = self.side_schemas(side);
+        let previous = match schema {
+            Some(schema) => schemas.insert(name.to_string(), schema),
+            None => schemas.remove(name),
+        };
+        changes.push(Change::Schema(side, name.to_string(), previous));
+    }
+
+    /// Marks `name` as registered as a dependency or not.
+    fn set_dependency_name(&mut self, name: &str, dependency: bool, changes: &mut Vec<Change>) {
+        let previous = if dependency {
+            !self.dependency_names.insert(name.to_string())
+        } else {
+            self.dependency_names.remove(name)
+        };
+        changes.push(Change::DependencyName(name.to_string(), previous));
+    }
+
+    /// Reverts `changes`, newest first.
+    fn undo(&mut self, changes: Vec<Change>) {
+        for change in changes.into_iter().rev() {
+            match change {
+                Change::Schema(side, name, Some(schema)) => {
+                    self.side_schemas(side).insert(name, schema);
+                }
+                Change::Schema(side, name, None) => {
+                    self.side_schemas(side).remove(&name);
+                }
+                Change::DependencyName(name, true) => {
+                    self.dependency_names.insert(name);
+                }
+                Change::DependencyName(name, false) => {
+                    self.dependency_names.remove(&name);
+                }
+            }
+        }
+    }
+
+    /// The schema registered as `name`, on either side.
+    fn registered(&self, name: &str) -> Option<&ZodType> {
+        self.schemas
+            .get(name)
+            .or_else(|| self.input_schemas.get(name))
+    }
+
+    fn side_schemas(&mut self, side: Side) -> &mut BTreeMap<String, ZodType> {
+        match side {
+            Side::Output => &mut self.schemas,
+            Side::Input => &mut self.input_schemas,
+        }
+    }
+
+    /// Whether the named type with the given id is registered on `side` under
+    /// any name.
+    fn is_registered(&self, id: &str, side: Side) -> bool {
+        let schemas = match side {
+            Side::Output => &self.schemas,
+            Side::Input => &self.input_schemas,
+        };
+        schemas
+            .values()
+            .any(|schema| matches!(schema, ZodType::Named { id: other, .. } if other == id))
+    }
+
+    /// The schemas to generate, keyed by export name.
+    ///
+    /// A schema whose input and output differ is exported twice, as
+    /// `<name>Input` and `<name>Output`.
+    pub(crate) fn exports(&self) -> BTreeMap<String, Export<'_>> {
+        let mut exports = BTreeMap::new();
+        let names: BTreeSet<&String> = self
+            .schemas
+            .keys()
+            .chain(self.input_schemas.keys())
+            .collect();
+        for name in names {
+            let export = |schema, side| Export {
+                registered: name,
+                schema,
+                side,
+            };
+            match (self.schemas.get(name), self.input_schemas.get(name)) {
+                (Some(output), Some(input)) if output != input => {
+                    exports.insert(format!("{name}Input"), export(input, Some(Side::Input)));
+                    exports.insert(format!("{name}Output"), export(output, Some(Side::Output)));
+                }
+                (Some(output), Some(_)) => {
+                    exports.insert(name.clone(), export(output, None));
+                }
+                (Some(output), None) => {
+                    exports.insert(name.clone(), export(output, Some(Side::Output)));
+                }
+                (None, Some(input)) => {
+                    exports.insert(name.clone(), export(input, Some(Side::Input)));
+                }
+                (None, None) => unreachable!("every name is registered on some side"),
+            }
+        }
+        exports
     }
 
     /// Generate Zod schemas file
@@ -202,14 +907,259 @@ impl ZodGenerator {
         let mut output =
             String::from("// Automatically generated by zod_gen\nimport * as z from 'zod';\n\n");
 
-        for (name, schema) in &self.schemas {
+        for (name, export) in &self.exports() {
+            let schema = export.schema;
+            let rendered = self.export_schema(name, schema).to_zod();
+            output.push_str(&self.render_export(name, schema, &rendered));
+        }
+
+        output
+    }
+
+    /// Also emit a constant object for every registered enum schema
+    ///
+    /// For a `z.enum` schema `Status`, this adds
+    /// `export const Status = { Active: 'active', ... } as const;` next to
+    /// `StatusSchema`, so frontend code can refer to variants by name. Applies
+    /// to [`generate`](Self::generate) and [`generate_modules`](Self::generate_modules).
+    pub fn enum_constants(&mut self, enabled: bool) -> &mut Self {
+        self.enum_constants = enabled;
+        self
+    }
+
+    /// Attach the export name as `id` metadata to every exported schema
+    ///
+    /// Each `XSchema` becomes `<schema>.meta({"id":"X"})`, so that Zod 4's
+    /// global registry and `z.toJSONSchema` know it by its name. Requires
+    /// Zod 4. Applies to [`generate`](Self::generate) and
+    /// [`generate_modules`](Self::generate_modules).
+    pub fn schema_ids(&mut self, enabled: bool) -> &mut Self {
+        self.schema_ids = enabled;
+        self
+    }
+
+    /// The unnamed schema of an export, with its id if `schema_ids` is
+    /// enabled. Schemas inlined elsewhere don't get one, as Zod rejects
+    /// duplicate ids.
+    pub(crate) fn export_schema(&self, name: &str, schema: &ZodType) -> ZodType {
+        let schema = schema.unnamed();
+        if !self.schema_ids {
+            return schema.clone();
+        }
+        let (inner, mut meta) = match schema {
+            ZodType::Meta(inner, meta) => ((**inner).clone(), meta.clone()),
+            other => (other.clone(), serde_json::Map::new()),
+        };
+        meta.insert("id".to_string(), serde_json::Value::from(name));
+        ZodType::meta(inner, meta)
+    }
+
+    /// Renders the exports of one registered schema, given its rendered Zod
+    /// expression.
+    pub(crate) fn render_export(&self, name: &str, schema: &ZodType, rendered: &str) -> String {
+        let doc = match schema.deprecation() {
+            Some(note) => format!("{}\n", jsdoc_deprecated(note)),
+            None => String::new(),
+        };
+        let member_doc = |deprecated: &Option<String>| match deprecated {
+            Some(note) => format!("  {}\n", jsdoc_deprecated(note)),
+            None => String::new(),
+        };
+        let mut output = format!(
+            "{doc}export const {name}Schema = {rendered};\n{doc}export type {name} = z.infer<typeof {name}Schema>;\n"
+        );
+        // Numeric values are meaningless without their names, so numeric
+        // enums always get their constant object.
+        let members: Option<String> = match schema.shape() {
+            ZodType::Enum(variants) if self.enum_constants => Some(
+                variants
+                    .iter()
+                    .map(|v| {
+                        format!(
+                            "{}  {}: '{}',\n",
+                            member_doc(&v.deprecated),
+                            v.name,
+                            v.value
+                        )
+                    })
+                    .collect(),
+            ),
+            ZodType::NumericEnum(variants) => Some(
+                variants
+                    .iter()
+                    .map(|v| format!("{}  {}: {},\n", member_doc(&v.deprecated), v.name, v.value))
+                    .collect(),
+            ),
+            _ => None,
+        };
+        if let Some(members) = members {
             output.push_str(&format!(
-                "export const {name}Schema = {schema};\nexport type {name} = z.infer<typeof {name}Schema>;\n\n"
+                "{doc}export const {name} = {{\n{members}}} as const;\n"
             ));
         }
+        output.push('\n');
+        output
+    }
+
+    /// Write the generated Zod schemas file to `path`
+    ///
+    /// The file is only written when its content changed, so build scripts
+    /// don't trigger needless rebuilds of the frontend. Returns whether the
+    /// file was written.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> std::io::Result<bool> {
+        output::write_if_changed(path.as_ref(), &self.generate())
+    }
+
+    /// Check that the file at `path` matches the generated Zod schemas
+    ///
+    /// Intended for CI: a test calling `check` fails with a readable diff
+    /// when the committed file is stale.
+    ///
+    /// ```rust,ignore
+    /// #[test]
+    /// fn schemas_are_up_to_date() {
+    ///     if let Err(err) = generator().check("frontend/src/schemas.ts") {
+    ///         panic!("{err}");
+    ///     }
+    /// }
+    /// ```
+    pub fn check(&self, path: impl AsRef<Path>) -> Result<(), CheckError> {
+        output::check(path.as_ref(), &self.generate())
+    }
+
+    /// Put the schema registered as `name` into `module` for multi-file output
+    ///
+    /// Modules are slash-separated paths without extension, e.g.
+    /// `"api/users"`. Schemas without an explicit module follow the Rust
+    /// module path of their type, see [`generate_modules`](Self::generate_modules).
+    pub fn set_module(&mut self, name: &str, module: &str) -> &mut Self {
+        self.modules.insert(name.to_string(), module.to_string());
+        self
+    }
+
+    /// Generate one Zod schemas file per module
+    ///
+    /// Each schema goes into the module set with [`set_module`](Self::set_module),
+    /// or else the Rust module path of its derived type without the crate name
+    /// (`my_app::dto::user::User` goes to `dto/user.ts`, types at the crate
+    /// root to `my_app.ts`), or else [`ModuleOptions::default_module`].
+    ///
+    /// Registered types referenced from another schema are imported from their
+    /// module instead of being inlined, and an `index.ts` barrel re-exports
+    /// every module. Returns the file contents keyed by their path relative to
+    /// the output directory, or [`RegistrationError::ReservedModule`] if a
+    /// schema would go into the `index` module.
+    pub fn generate_modules(
+        &self,
+        options: &ModuleOptions,
+    ) -> Result<BTreeMap<String, String>, RegistrationError> {
+        modules::generate(self, options)
+    }
+
+    /// Write the files from [`generate_modules`](Self::generate_modules) into `dir`
+    ///
+    /// Like [`write_to`](Self::write_to), files are only written when their
+    /// content changed. Returns the number of files written; an error from
+    /// `generate_modules` is returned as [`std::io::ErrorKind::InvalidInput`].
+    pub fn write_modules_to(
+        &self,
+        dir: impl AsRef<Path>,
+        options: &ModuleOptions,
+    ) -> std::io::Result<usize> {
+        let files = self
+            .generate_modules(options)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        let mut written = 0;
+        for (file, content) in files {
+            if output::write_if_changed(&dir.as_ref().join(file), &content)? {
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Generate plain TypeScript declarations
+    ///
+    /// Creates a `.d.ts`-style file with an `export interface` for every
+    /// object schema and an `export type` alias for everything else. The
+    /// declarations describe the same values as the Zod schemas from
+    /// [`generate`](Self::generate), without depending on Zod. Nested derived
+    /// types that are registered with the generator are referenced by their
+    /// export name.
+    pub fn generate_declarations(&self) -> String {
+        let mut output = String::from("// Automatically generated by zod_gen\n\n");
+
+        for (name, export) in &self.exports() {
+            let names = self.export_names(export.names_side());
+            output.push_str(&typescript::render_declaration(name, export.schema, &names));
+            output.push('\n');
+        }
 
         output
     }
+
+    /// Generate a JSON Schema document
+    ///
+    /// Returns a JSON Schema (draft 2020-12) document with one `$defs` entry
+    /// per registered schema. Nested derived types that are registered with
+    /// the generator are referenced via `$ref`; discriminated unions become
+    /// `oneOf` with `const` tags.
+    pub fn generate_json_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "$schema": json_schema::DIALECT,
+            "$defs": self.json_schema_definitions(json_schema::DEFS_PREFIX),
+        })
+    }
+
+    /// Generate an OpenAPI 3.1 `components` object
+    ///
+    /// Returns `{ "schemas": { ... } }` with one entry per registered schema.
+    /// References between registered types point to `#/components/schemas/`.
+    /// Use [`openapi::OpenApi`] to produce a complete spec with paths.
+    pub fn generate_openapi_components(&self) -> serde_json::Value {
+        serde_json::json!({
+            "schemas": self.json_schema_definitions(openapi::COMPONENTS_PREFIX),
+        })
+    }
+
+    /// Renders every registered schema as a JSON Schema, keyed by export name.
+    ///
+    /// References to registered types point to `<ref_prefix><name>`.
+    fn json_schema_definitions(
+        &self,
+        ref_prefix: &str,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let output_names = self.export_names(Side::Output);
+        let input_names = self.export_names(Side::Input);
+        self.exports()
+            .into_iter()
+            .map(|(name, export)| {
+                let refs = json_schema::Refs {
+                    names: match export.names_side() {
+                        Side::Output => &output_names,
+                        Side::Input => &input_names,
+                    },
+                    prefix: ref_prefix,
+                };
+                let definition = json_schema::render_definition(export.schema, &refs);
+                (name, definition)
+            })
+            .collect()
+    }
+
+    /// Maps the id of every registered named type to the export name that
+    /// describes it on `side`.
+    pub(crate) fn export_names(&self, side: Side) -> BTreeMap<String, String> {
+        self.exports()
+            .into_iter()
+            .filter_map(|(name, export)| match export.schema {
+                ZodType::Named { id, .. } if export.side.is_none_or(|s| s == side) => {
+                    Some((id.clone(), name))
+                }
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 // Implementations for common Rust types
@@ -217,99 +1167,187 @@ impl ZodSchema for String {
     fn zod_schema() -> String {
         zod_string().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::String
+    }
 }
 
 impl ZodSchema for i32 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for i64 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for u32 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for u64 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for f32 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for f64 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for bool {
     fn zod_schema() -> String {
         zod_boolean().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Boolean
+    }
 }
 
 impl ZodSchema for u8 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for u16 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for i8 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl ZodSchema for i16 {
     fn zod_schema() -> String {
         zod_number().to_string()
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::Number
+    }
 }
 
 impl<T: ZodSchema> ZodSchema for Option<T> {
     fn zod_schema() -> String {
         zod_nullable(&T::zod_schema())
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::nullable(T::zod_type())
+    }
+
+    fn zod_input_type() -> ZodType {
+        ZodType::nullable(T::zod_input_type())
+    }
 }
 
 impl<T: ZodSchema> ZodSchema for Vec<T> {
     fn zod_schema() -> String {
         zod_array(&T::zod_schema())
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::array(T::zod_type())
+    }
+
+    fn zod_input_type() -> ZodType {
+        ZodType::array(T::zod_input_type())
+    }
 }
 
 impl<T: ZodSchema> ZodSchema for HashMap<String, T> {
     fn zod_schema() -> String {
         zod_record(&T::zod_schema())
     }
+
+    fn zod_type() -> ZodType {
+        ZodType::record(T::zod_type())
+    }
+
+    fn zod_input_type() -> ZodType {
+        ZodType::record(T::zod_input_type())
+    }
+}
+
+impl<T: ZodSchema> ZodObjectSchema for HashMap<String, T> {
+    fn object_fields() -> Vec<ZodField> {
+        Vec::new()
+    }
+
+    fn catchall() -> Option<ZodType> {
+        Some(T::zod_type())
+    }
+
+    fn input_catchall() -> Option<ZodType> {
+        Some(T::zod_input_type())
+    }
 }
 
 impl ZodSchema for serde_json::Value {
     fn zod_schema() -> String {
         "z.any()".to_string()
     }
-}
 
-impl<T: ZodSchema> ZodObjectSchema for T {}
+    fn zod_type() -> ZodType {
+        ZodType::Any
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -321,6 +1359,24 @@ mod tests {
         assert_eq!(zod_number(), "z.number()");
         assert_eq!(zod_boolean(), "z.boolean()");
         assert_eq!(zod_bigint(), "z.bigint()");
+        assert_eq!(zod_enum(&["a", "b"]), "z.enum(['a', 'b'])");
+        assert_eq!(
+            zod_numeric_enum(&[0, 1]),
+            "z.union([z.literal(0), z.literal(1)])"
+        );
+        assert_eq!(zod_numeric_enum(&[-1]), "z.literal(-1)");
+        assert_eq!(
+            zod_string_except(&["a", "b"]),
+            "z.string().refine((value) => !['a', 'b'].includes(value))"
+        );
+        assert_eq!(
+            zod_default("z.number()", &serde_json::json!(20)),
+            "z.number().default(20)"
+        );
+        assert_eq!(
+            zod_brand("z.string()", "UserId"),
+            "z.string().brand<\"UserId\">()"
+        );
 
         assert_eq!(i8::zod_schema(), "z.number()");
         assert_eq!(i16::zod_schema(), "z.number()");
@@ -362,6 +1418,208 @@ mod tests {
         assert!(output.contains("DummySchema = z.string()"));
     }
 
+    #[test]
+    fn test_zod_type_matches_zod_schema() {
+        assert_eq!(
+            <Option<Vec<i32>>>::zod_type().to_zod(),
+            <Option<Vec<i32>>>::zod_schema()
+        );
+        assert_eq!(
+            <HashMap<String, bool>>::zod_type().to_zod(),
+            <HashMap<String, bool>>::zod_schema()
+        );
+        assert_eq!(Dummy::zod_type(), ZodType::Raw("z.string()".into()));
+    }
+
+    #[test]
+    fn test_generate_declarations() {
+        let mut gen = ZodGenerator::new();
+        gen.add_schema::<Dummy>("Dummy");
+        gen.add_schema::<Vec<Option<String>>>("Names");
+        let output = gen.generate_declarations();
+        assert!(output.contains("// Automatically generated by zod_gen"));
+        assert!(!output.contains("import"));
+        assert!(output.contains("export type Dummy = unknown;"));
+        assert!(output.contains("export type Names = (string | null)[];"));
+    }
+
+    #[test]
+    fn test_generate_json_schema() {
+        let mut gen = ZodGenerator::new();
+        gen.add_schema::<Vec<Option<String>>>("Names");
+        let output = gen.generate_json_schema();
+        assert_eq!(
+            output["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        assert_eq!(
+            output["$defs"]["Names"],
+            serde_json::json!({
+                "type": "array",
+                "items": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            })
+        );
+    }
+
+    #[test]
+    fn test_write_to_and_check() {
+        let dir = std::env::temp_dir().join(format!("zod_gen_write_to_{}", std::process::id()));
+        let path = dir.join("nested").join("schemas.ts");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut gen = ZodGenerator::new();
+        gen.add_schema::<Dummy>("Dummy");
+        assert!(matches!(gen.check(&path), Err(CheckError::Missing { .. })));
+
+        assert!(gen.write_to(&path).unwrap());
+        assert!(!gen.write_to(&path).unwrap());
+        assert!(gen.check(&path).is_ok());
+
+        gen.add_schema::<String>("Name");
+        match gen.check(&path) {
+            Err(CheckError::Stale { diff, .. }) => {
+                assert!(diff.contains(&DiffLine::Added(
+                    "export const NameSchema = z.string();".into()
+                )));
+            }
+            other => panic!("expected a stale file, got {other:?}"),
+        }
+        assert!(gen.write_to(&path).unwrap());
+        assert!(gen.check(&path).is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    struct Leaf;
+
+    impl ZodSchema for Leaf {
+        fn zod_schema() -> String {
+            Self::zod_type().to_zod()
+        }
+
+        fn zod_type() -> ZodType {
+            ZodType::named("tests::Leaf", "Leaf", ZodType::String)
+        }
+    }
+
+    struct Branch;
+
+    impl ZodSchema for Branch {
+        fn zod_schema() -> String {
+            Self::zod_type().to_zod()
+        }
+
+        fn zod_type() -> ZodType {
+            ZodType::named(
+                "tests::Branch",
+                "Branch",
+                ZodType::object(vec![
+                    ZodField::new("leaf", Leaf::zod_type()),
+                    ZodField::new("leaves", <Vec<Leaf>>::zod_type()),
+                ]),
+            )
+        }
+    }
+
+    #[test]
+    fn test_type_name_and_dependencies() {
+        assert_eq!(Branch::type_name().as_deref(), Some("Branch"));
+        assert_eq!(<Vec<Branch>>::type_name(), None);
+        assert_eq!(Branch::dependencies(), vec![Leaf::zod_type()]);
+        assert_eq!(<Vec<Branch>>::dependencies(), vec![Branch::zod_type()]);
+    }
+
+    #[test]
+    fn test_add_schema_registers_dependencies() {
+        let mut gen = ZodGenerator::new();
+        gen.add_schema::<Vec<Branch>>("Tree");
+        let names: Vec<&String> = gen.schemas.keys().collect();
+        assert_eq!(names, ["Branch", "Leaf", "Tree"]);
+
+        // An explicit name replaces the automatic one
+        gen.add_schema::<Leaf>("Node");
+        let names: Vec<&String> = gen.schemas.keys().collect();
+        assert_eq!(names, ["Branch", "Node", "Tree"]);
+        assert!(gen
+            .generate_declarations()
+            .contains("export interface Branch {\n  leaf: Node;\n  leaves: Node[];\n}"));
+    }
+
+    struct OtherLeaf;
+
+    impl ZodSchema for OtherLeaf {
+        fn zod_schema() -> String {
+            Self::zod_type().to_zod()
+        }
+
+        fn zod_type() -> ZodType {
+            ZodType::named("tests::other::Leaf", "Leaf", ZodType::Number)
+        }
+    }
+
+    #[test]
+    fn test_add_uses_type_name() {
+        let mut gen = ZodGenerator::new();
+        gen.add::<Branch>().unwrap().add::<Leaf>().unwrap();
+        let names: Vec<&String> = gen.schemas.keys().collect();
+        assert_eq!(names, ["Branch", "Leaf"]);
+
+        assert_eq!(
+            gen.add::<Vec<Leaf>>().unwrap_err(),
+            RegistrationError::MissingName {
+                rust_type: "alloc::vec::Vec<zod_gen::tests::Leaf>"
+            }
+        );
+    }
+
+    #[test]
+    fn test_duplicate_names_are_reported() {
+        let mut gen = ZodGenerator::new();
+        gen.add::<Branch>().unwrap();
+        // Re-adding the same type under the same name is fine
+        gen.add::<Branch>().unwrap();
+
+        let err = gen.add::<OtherLeaf>().unwrap_err();
+        assert_eq!(
+            err,
+            RegistrationError::DuplicateName {
+                name: "Leaf".into(),
+                existing: "`tests::Leaf`".into(),
+                new: "`tests::other::Leaf`".into(),
+            }
+        );
+        assert!(err.to_string().contains("#[zod(rename"));
+
+        // The generator is unchanged and the conflict can be resolved by naming
+        assert_eq!(gen.schemas["Leaf"], Leaf::zod_type());
+        gen.try_add_schema::<OtherLeaf>("OtherLeaf").unwrap();
+        assert!(gen
+            .try_add_schema::<Dummy>("Branch")
+            .unwrap_err()
+            .to_string()
+            .contains("`Branch` is used by both `tests::Branch` and `z.string()`"));
+    }
+
+    #[test]
+    fn test_failed_registration_is_undone() {
+        let mut gen = ZodGenerator::new();
+        gen.try_add_schema::<OtherLeaf>("Leaf").unwrap();
+        // `Branch` is inserted before its dependency `Leaf` conflicts
+        gen.add::<Branch>().unwrap_err();
+        assert_eq!(gen.schemas.keys().collect::<Vec<_>>(), ["Leaf"]);
+        assert_eq!(gen.input_schemas.keys().collect::<Vec<_>>(), ["Leaf"]);
+        assert!(gen.dependency_names.is_empty());
+        assert_eq!(gen.schemas["Leaf"], OtherLeaf::zod_type());
+    }
+
+    #[test]
+    #[should_panic(expected = "export name `Leaf` is used by both")]
+    fn test_add_schema_panics_on_duplicate_dependency() {
+        let mut gen = ZodGenerator::new();
+        gen.add_schema::<OtherLeaf>("Leaf");
+        gen.add_schema::<Branch>("Branch");
+    }
+
     #[test]
     fn test_generic_hashmap() {
         // Test HashMap<String, String>
@@ -0,0 +1,301 @@
+//! Splitting the generated Zod schemas into multiple TypeScript modules.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{RegistrationError, Side, ZodGenerator, ZodType};
+
+/// Module name of the generated barrel file re-exporting every module.
+const BARREL_MODULE: &str = "index";
+
+/// Options for [`ZodGenerator::generate_modules`].
+#[derive(Debug, Clone)]
+pub struct ModuleOptions {
+    esm_imports: bool,
+    default_module: String,
+}
+
+impl Default for ModuleOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModuleOptions {
+    pub fn new() -> Self {
+        Self {
+            esm_imports: false,
+            default_module: "types".to_string(),
+        }
+    }
+
+    /// Append `.js` to relative import paths, as ESM module resolution requires.
+    pub fn esm_imports(mut self, enabled: bool) -> Self {
+        self.esm_imports = enabled;
+        self
+    }
+
+    /// Module for schemas that have neither an explicit module nor a Rust
+    /// module path, e.g. `Vec<User>` registered as `"UserList"`.
+    ///
+    /// Defaults to `"types"`.
+    pub fn default_module(mut self, module: &str) -> Self {
+        self.default_module = module.to_string();
+        self
+    }
+}
+
+/// Renders one TypeScript file per module plus an `index.ts` barrel.
+///
+/// Returns the file contents keyed by their path relative to the output
+/// directory, e.g. `"dto/user.ts"`.
+pub(crate) fn generate(
+    generator: &ZodGenerator,
+    options: &ModuleOptions,
+) -> Result<BTreeMap<String, String>, RegistrationError> {
+    let exported = generator.exports();
+    let output_names = generator.export_names(Side::Output);
+    let input_names = generator.export_names(Side::Input);
+    let names_of = |side| match side {
+        Side::Output => &output_names,
+        Side::Input => &input_names,
+    };
+
+    // Module and registered dependencies of every export.
+    let mut module_of: BTreeMap<&str, String> = BTreeMap::new();
+    let mut deps_of: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (name, export) in &exported {
+        module_of.insert(
+            name,
+            module_for(generator, export.registered, export.schema, options),
+        );
+        let mut deps = BTreeSet::new();
+        collect_dependencies(
+            export.schema.unnamed(),
+            names_of(export.names_side()),
+            &mut deps,
+        );
+        deps_of.insert(name, deps);
+    }
+
+    if let Some((name, _)) = module_of.iter().find(|(_, m)| *m == BARREL_MODULE) {
+        return Err(RegistrationError::ReservedModule {
+            name: name.to_string(),
+        });
+    }
+
+    // Which modules import which, to detect import cycles between files.
+    let mut module_imports: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (name, deps) in &deps_of {
+        let module = module_of[name].as_str();
+        let imports = module_imports.entry(module).or_default();
+        for dep in deps {
+            if module_of[dep] != module {
+                imports.insert(module_of[dep].as_str());
+            }
+        }
+    }
+
+    let mut exports_by_module: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (name, module) in &module_of {
+        exports_by_module.entry(module).or_default().push(name);
+    }
+
+    let mut files = BTreeMap::new();
+    for (module, exports) in &exports_by_module {
+        let mut imports: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut body = String::new();
+
+        for name in declaration_order(exports, &deps_of, &module_of, module) {
+            let export = &exported[name];
+            let schema = export.schema;
+            let names = names_of(export.names_side());
+            let rendered = generator
+                .export_schema(name, schema)
+                .to_zod_with(&mut |id| {
+                    let target = names.get(id)?;
+                    let target_module = module_of[target.as_str()].as_str();
+                    let reference = format!("{target}Schema");
+                    if target_module == *module {
+                        return Some(reference);
+                    }
+                    imports
+                        .entry(import_path(module, target_module, options))
+                        .or_default()
+                        .insert(reference.clone());
+                    // Imports between files that import each other are not
+                    // initialized yet when the module body runs, so defer them.
+                    if reaches(&module_imports, target_module, module) {
+                        Some(format!("z.lazy(() => {reference})"))
+                    } else {
+                        Some(reference)
+                    }
+                });
+            body.push_str(&generator.render_export(name, schema, &rendered));
+        }
+
+        let mut output =
+            String::from("// Automatically generated by zod_gen\nimport * as z from 'zod';\n");
+        for (path, symbols) in &imports {
+            let symbols: Vec<&str> = symbols.iter().map(String::as_str).collect();
+            output.push_str(&format!(
+                "import {{ {} }} from '{path}';\n",
+                symbols.join(", ")
+            ));
+        }
+        output.push('\n');
+        output.push_str(&body);
+        files.insert(format!("{module}.ts"), output);
+    }
+
+    let mut barrel = String::from("// Automatically generated by zod_gen\n");
+    for module in exports_by_module.keys() {
+        barrel.push_str(&format!(
+            "export * from '{}';\n",
+            import_path(BARREL_MODULE, module, options)
+        ));
+    }
+    files.insert(format!("{BARREL_MODULE}.ts"), barrel);
+
+    Ok(files)
+}
+
+/// Picks the module of a registered schema: the explicit module if one was
+/// set, otherwise the Rust module path of a derived type without the crate
+/// name, otherwise the default module.
+fn module_for(
+    generator: &ZodGenerator,
+    name: &str,
+    schema: &ZodType,
+    options: &ModuleOptions,
+) -> String {
+    if let Some(module) = generator.modules.get(name) {
+        return module.clone();
+    }
+    match schema {
+        ZodType::Named { id, .. } => {
+            let segments: Vec<&str> = id.split("::").collect();
+            match segments.as_slice() {
+                [] | [_] => options.default_module.clone(),
+                [krate, _] => krate.to_string(),
+                [_, path @ .., _] => path.join("/"),
+            }
+        }
+        _ => options.default_module.clone(),
+    }
+}
+
+/// Collects the export names of registered types referenced by `schema`.
+fn collect_dependencies<'a>(
+    schema: &ZodType,
+    names: &'a BTreeMap<String, String>,
+    deps: &mut BTreeSet<&'a str>,
+) {
+    schema.visit_named(&mut |named| {
+        if let ZodType::Named { id, schema, .. } = named {
+            match names.get(id) {
+                Some(name) => {
+                    deps.insert(name);
+                }
+                None => collect_dependencies(schema, names, deps),
+            }
+        }
+    });
+}
+
+/// Orders the exports of a module so that every schema is declared after the
+/// schemas of the same module it references.
+fn declaration_order<'a>(
+    exports: &[&'a str],
+    deps_of: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+    module_of: &BTreeMap<&str, String>,
+    module: &str,
+) -> Vec<&'a str> {
+    fn visit<'a>(
+        name: &'a str,
+        deps_of: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+        module_of: &BTreeMap<&str, String>,
+        module: &str,
+        ordered: &mut Vec<&'a str>,
+    ) {
+        if ordered.contains(&name) {
+            return;
+        }
+        for dep in &deps_of[name] {
+            if module_of[dep] == module && *dep != name {
+                visit(dep, deps_of, module_of, module, ordered);
+            }
+        }
+        ordered.push(name);
+    }
+
+    let mut ordered = Vec::with_capacity(exports.len());
+    for name in exports {
+        visit(name, deps_of, module_of, module, &mut ordered);
+    }
+    ordered
+}
+
+/// Whether module `from` transitively imports module `to`.
+fn reaches(module_imports: &BTreeMap<&str, BTreeSet<&str>>, from: &str, to: &str) -> bool {
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![from];
+    while let Some(module) = stack.pop() {
+        if module == to {
+            return true;
+        }
+        if seen.insert(module) {
+            if let Some(imports) = module_imports.get(module) {
+                stack.extend(imports.iter().copied());
+            }
+        }
+    }
+    false
+}
+
+/// Relative import specifier of module `to` from module `from`.
+fn import_path(from: &str, to: &str, options: &ModuleOptions) -> String {
+    let from_dir: Vec<&str> = from.split('/').collect();
+    let from_dir = &from_dir[..from_dir.len() - 1];
+    let to: Vec<&str> = to.split('/').collect();
+
+    let common = from_dir
+        .iter()
+        .zip(&to[..to.len() - 1])
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut path = if common == from_dir.len() {
+        "./".to_string()
+    } else {
+        "../".repeat(from_dir.len() - common)
+    };
+    path.push_str(&to[common..].join("/"));
+    if options.esm_imports {
+        path.push_str(".js");
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_path() {
+        let options = ModuleOptions::new();
+        assert_eq!(import_path("user", "address", &options), "./address");
+        assert_eq!(
+            import_path("dto/user", "dto/address", &options),
+            "./address"
+        );
+        assert_eq!(import_path("dto/user", "common", &options), "../common");
+        assert_eq!(
+            import_path("dto/user", "shared/geo/address", &options),
+            "../shared/geo/address"
+        );
+        assert_eq!(import_path("index", "dto/user", &options), "./dto/user");
+        assert_eq!(
+            import_path("index", "dto/user", &ModuleOptions::new().esm_imports(true)),
+            "./dto/user.js"
+        );
+    }
+}
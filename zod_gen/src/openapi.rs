@@ -0,0 +1,382 @@
+//! OpenAPI 3.1 documents built from the types registered with a [`ZodGenerator`].
+//!
+//! ```rust
+//! use zod_gen::openapi::{OpenApi, Operation};
+//! use zod_gen::ZodGenerator;
+//!
+//! let mut generator = ZodGenerator::new();
+//! generator.add_schema::<String>("UserName");
+//!
+//! let mut api = OpenApi::new("Users API", "1.0.0");
+//! api.operation(
+//!     Operation::get("/users/{id}")
+//!         .operation_id("getUserName")
+//!         .path_param::<u64>("id")
+//!         .response::<String>(200, "The user's name"),
+//! );
+//!
+//! let document = api.to_json(&generator);
+//! assert_eq!(document["openapi"], "3.1.0");
+//! assert!(document["components"]["schemas"]["UserName"].is_object());
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::{json_schema, Side, ZodGenerator, ZodSchema, ZodType};
+
+/// Version of the OpenAPI specification the documents follow.
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Where `$ref`s to registered types point in an OpenAPI document.
+pub(crate) const COMPONENTS_PREFIX: &str = "#/components/schemas/";
+
+/// HTTP method of an [`Operation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Method {
+    fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Post => "post",
+            Method::Put => "put",
+            Method::Patch => "patch",
+            Method::Delete => "delete",
+        }
+    }
+}
+
+/// Location of an operation [`Parameter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParameterLocation {
+    Path,
+    Query,
+}
+
+#[derive(Debug, Clone)]
+struct Parameter {
+    name: String,
+    location: ParameterLocation,
+    schema: ZodType,
+}
+
+#[derive(Debug, Clone)]
+struct Response {
+    status: u16,
+    description: String,
+    body: Option<ZodType>,
+}
+
+/// A single API operation, i.e. a method on a path.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    method: Method,
+    path: String,
+    operation_id: Option<String>,
+    summary: Option<String>,
+    parameters: Vec<Parameter>,
+    request: Option<ZodType>,
+    responses: Vec<Response>,
+}
+
+impl Operation {
+    pub fn new(method: Method, path: &str) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            operation_id: None,
+            summary: None,
+            parameters: Vec::new(),
+            request: None,
+            responses: Vec::new(),
+        }
+    }
+
+    pub fn get(path: &str) -> Self {
+        Self::new(Method::Get, path)
+    }
+
+    pub fn post(path: &str) -> Self {
+        Self::new(Method::Post, path)
+    }
+
+    pub fn put(path: &str) -> Self {
+        Self::new(Method::Put, path)
+    }
+
+    pub fn patch(path: &str) -> Self {
+        Self::new(Method::Patch, path)
+    }
+
+    pub fn delete(path: &str) -> Self {
+        Self::new(Method::Delete, path)
+    }
+
+    pub fn operation_id(mut self, id: &str) -> Self {
+        self.operation_id = Some(id.to_string());
+        self
+    }
+
+    pub fn summary(mut self, summary: &str) -> Self {
+        self.summary = Some(summary.to_string());
+        self
+    }
+
+    /// Adds a required path parameter, e.g. `id` for `/users/{id}`.
+    pub fn path_param<T: ZodSchema>(mut self, name: &str) -> Self {
+        self.parameters.push(Parameter {
+            name: name.to_string(),
+            location: ParameterLocation::Path,
+            schema: T::zod_input_type(),
+        });
+        self
+    }
+
+    /// Adds a query parameter. `Option<T>` parameters are not required.
+    pub fn query_param<T: ZodSchema>(mut self, name: &str) -> Self {
+        self.parameters.push(Parameter {
+            name: name.to_string(),
+            location: ParameterLocation::Query,
+            schema: T::zod_input_type(),
+        });
+        self
+    }
+
+    /// Sets the JSON request body, described by the type's input schema.
+    pub fn request<T: ZodSchema>(mut self) -> Self {
+        self.request = Some(T::zod_input_type());
+        self
+    }
+
+    /// Adds a response with a JSON body.
+    pub fn response<T: ZodSchema>(mut self, status: u16, description: &str) -> Self {
+        self.responses.push(Response {
+            status,
+            description: description.to_string(),
+            body: Some(T::zod_type()),
+        });
+        self
+    }
+
+    /// Adds a response without a body, e.g. `204 No Content`.
+    pub fn empty_response(mut self, status: u16, description: &str) -> Self {
+        self.responses.push(Response {
+            status,
+            description: description.to_string(),
+            body: None,
+        });
+        self
+    }
+
+    /// Renders the operation; `input_refs` resolve types in parameters and
+    /// the request body, `refs` those in responses.
+    fn render(&self, input_refs: &json_schema::Refs<'_>, refs: &json_schema::Refs<'_>) -> Value {
+        let mut operation = Map::new();
+        if let Some(id) = &self.operation_id {
+            operation.insert("operationId".into(), json!(id));
+        }
+        if let Some(summary) = &self.summary {
+            operation.insert("summary".into(), json!(summary));
+        }
+        if !self.parameters.is_empty() {
+            let parameters: Vec<Value> = self
+                .parameters
+                .iter()
+                .map(|p| {
+                    let (location, required) = match p.location {
+                        ParameterLocation::Path => ("path", true),
+                        ParameterLocation::Query => {
+                            ("query", !matches!(p.schema, ZodType::Nullable(_)))
+                        }
+                    };
+                    let schema = match &p.schema {
+                        ZodType::Nullable(inner) if !required => inner.as_ref(),
+                        other => other,
+                    };
+                    json!({
+                        "name": p.name,
+                        "in": location,
+                        "required": required,
+                        "schema": json_schema::render_schema(schema, input_refs),
+                    })
+                })
+                .collect();
+            operation.insert("parameters".into(), json!(parameters));
+        }
+        if let Some(request) = &self.request {
+            operation.insert(
+                "requestBody".into(),
+                json!({
+                    "required": true,
+                    "content": json_content(request, input_refs),
+                }),
+            );
+        }
+        let responses: Map<String, Value> = self
+            .responses
+            .iter()
+            .map(|r| {
+                let mut response = Map::new();
+                response.insert("description".into(), json!(r.description));
+                if let Some(body) = &r.body {
+                    response.insert("content".into(), json_content(body, refs));
+                }
+                (r.status.to_string(), Value::Object(response))
+            })
+            .collect();
+        operation.insert("responses".into(), Value::Object(responses));
+        Value::Object(operation)
+    }
+}
+
+fn json_content(schema: &ZodType, refs: &json_schema::Refs<'_>) -> Value {
+    json!({
+        "application/json": { "schema": json_schema::render_schema(schema, refs) }
+    })
+}
+
+/// An OpenAPI 3.1 document: API info plus the declared operations.
+///
+/// The `components.schemas` section is rendered from a [`ZodGenerator`], so
+/// the spec uses exactly the types the Zod schemas are generated from.
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    operations: Vec<Operation>,
+}
+
+impl OpenApi {
+    pub fn new(title: &str, version: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            version: version.to_string(),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Declares an operation.
+    pub fn operation(&mut self, operation: Operation) -> &mut Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Renders the document as JSON.
+    ///
+    /// Request and response types registered with `generator` are referenced
+    /// from `components.schemas`; other types are inlined. Request bodies and
+    /// parameters refer to the `<name>Input` schemas of types whose input and
+    /// output differ, responses to `<name>Output`.
+    pub fn to_json(&self, generator: &ZodGenerator) -> Value {
+        let names = generator.export_names(Side::Output);
+        let refs = json_schema::Refs {
+            names: &names,
+            prefix: COMPONENTS_PREFIX,
+        };
+        let input_names = generator.export_names(Side::Input);
+        let input_refs = json_schema::Refs {
+            names: &input_names,
+            prefix: COMPONENTS_PREFIX,
+        };
+
+        let mut paths: BTreeMap<&str, Map<String, Value>> = BTreeMap::new();
+        for operation in &self.operations {
+            paths.entry(&operation.path).or_default().insert(
+                operation.method.as_str().into(),
+                operation.render(&input_refs, &refs),
+            );
+        }
+
+        json!({
+            "openapi": OPENAPI_VERSION,
+            "info": { "title": self.title, "version": self.version },
+            "paths": paths,
+            "components": generator.generate_openapi_components(),
+        })
+    }
+
+    /// Renders the document as YAML.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self, generator: &ZodGenerator) -> String {
+        serde_norway::to_string(&self.to_json(generator))
+            .expect("a JSON value always serializes to YAML")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_rendering() {
+        let mut generator = ZodGenerator::new();
+        generator.add_schema::<Vec<String>>("Tags");
+
+        let mut api = OpenApi::new("Test", "0.1.0");
+        api.operation(
+            Operation::post("/items")
+                .summary("Create an item")
+                .query_param::<Option<bool>>("dry_run")
+                .request::<Vec<String>>()
+                .response::<u32>(201, "Created")
+                .empty_response(400, "Invalid item"),
+        );
+
+        let document = api.to_json(&generator);
+        let operation = &document["paths"]["/items"]["post"];
+        assert_eq!(operation["summary"], "Create an item");
+        assert_eq!(
+            operation["parameters"][0],
+            json!({
+                "name": "dry_run",
+                "in": "query",
+                "required": false,
+                "schema": { "type": "boolean" },
+            })
+        );
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["schema"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert_eq!(
+            operation["responses"]["201"]["content"]["application/json"]["schema"],
+            json!({ "type": "number" })
+        );
+        assert_eq!(
+            operation["responses"]["400"],
+            json!({ "description": "Invalid item" })
+        );
+        assert_eq!(
+            document["components"]["schemas"]["Tags"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+    }
+
+    #[test]
+    fn test_operations_share_paths() {
+        let mut api = OpenApi::new("Test", "0.1.0");
+        api.operation(Operation::get("/items").empty_response(200, "List"))
+            .operation(Operation::delete("/items").empty_response(204, "Deleted"));
+
+        let document = api.to_json(&ZodGenerator::new());
+        assert!(document["paths"]["/items"]["get"].is_object());
+        assert!(document["paths"]["/items"]["delete"].is_object());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_to_yaml() {
+        let api = OpenApi::new("Test", "0.1.0");
+        let yaml = api.to_yaml(&ZodGenerator::new());
+        assert!(yaml.contains("openapi: 3.1.0"), "yaml: {yaml}");
+        assert!(yaml.contains("title: Test"), "yaml: {yaml}");
+    }
+}
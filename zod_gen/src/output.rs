@@ -0,0 +1,260 @@
+//! Writing generated output to disk and checking committed files for staleness.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Lines of unchanged context shown around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Above this many line comparisons the diff falls back to replacing the
+/// whole changed region instead of computing a minimal diff.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Writes `content` to `path` unless the file already has exactly that content.
+///
+/// Returns whether the file was written. Missing parent directories are created.
+pub(crate) fn write_if_changed(path: &Path, content: &str) -> io::Result<bool> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == content => return Ok(false),
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    fs::write(path, content)?;
+    Ok(true)
+}
+
+/// Compares the file at `path` with the expected `content`.
+pub(crate) fn check(path: &Path, content: &str) -> Result<(), CheckError> {
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(CheckError::Missing {
+                path: path.to_path_buf(),
+            });
+        }
+        Err(source) => {
+            return Err(CheckError::Io {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+    };
+    if existing == content {
+        return Ok(());
+    }
+    Err(CheckError::Stale {
+        path: path.to_path_buf(),
+        diff: diff_lines(&existing, content),
+    })
+}
+
+/// Error returned by [`ZodGenerator::check`](crate::ZodGenerator::check).
+#[derive(Debug)]
+pub enum CheckError {
+    /// The file does not exist yet.
+    Missing { path: PathBuf },
+    /// The file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The file exists but differs from the generated output.
+    Stale { path: PathBuf, diff: Vec<DiffLine> },
+}
+
+/// One line of a line-by-line diff from the file on disk to the generated output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// The line is the same in both.
+    Same(String),
+    /// The line is only in the file on disk.
+    Removed(String),
+    /// The line is only in the generated output.
+    Added(String),
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckError::Missing { path } => write!(
+                f,
+                "{} does not exist; regenerate it with `ZodGenerator::write_to`",
+                path.display()
+            ),
+            CheckError::Io { path, source } => {
+                write!(f, "failed to read {}: {source}", path.display())
+            }
+            CheckError::Stale { path, diff } => {
+                writeln!(
+                    f,
+                    "{} is out of date; regenerate it with `ZodGenerator::write_to`",
+                    path.display()
+                )?;
+                write_hunks(f, diff)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CheckError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the changed lines with some context, `-` for the file on disk and
+/// `+` for the generated output.
+fn write_hunks(f: &mut fmt::Formatter<'_>, diff: &[DiffLine]) -> fmt::Result {
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+    if changed.is_empty() {
+        return writeln!(f, "(only line endings or the trailing newline differ)");
+    }
+    let visible = |i: usize| changed.iter().any(|&c| c.abs_diff(i) <= CONTEXT_LINES);
+
+    let mut old_line = 1;
+    let mut new_line = 1;
+    let mut in_hunk = false;
+    for (i, line) in diff.iter().enumerate() {
+        if visible(i) {
+            if !in_hunk {
+                writeln!(f, "@@ -{old_line} +{new_line} @@")?;
+                in_hunk = true;
+            }
+            match line {
+                DiffLine::Same(text) => writeln!(f, " {text}")?,
+                DiffLine::Removed(text) => writeln!(f, "-{text}")?,
+                DiffLine::Added(text) => writeln!(f, "+{text}")?,
+            }
+        } else {
+            in_hunk = false;
+        }
+        match line {
+            DiffLine::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+    Ok(())
+}
+
+/// Computes a line diff from `old` to `new`.
+pub(crate) fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut diff: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|l| DiffLine::Same(l.to_string()))
+        .collect();
+    if old_mid.len().saturating_mul(new_mid.len()) <= MAX_DIFF_CELLS {
+        diff.extend(lcs_diff(old_mid, new_mid));
+    } else {
+        diff.extend(old_mid.iter().map(|l| DiffLine::Removed(l.to_string())));
+        diff.extend(new_mid.iter().map(|l| DiffLine::Added(l.to_string())));
+    }
+    diff.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|l| DiffLine::Same(l.to_string())),
+    );
+    diff
+}
+
+/// Minimal line diff based on the longest common subsequence.
+fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    // lengths[i][j] = LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            diff.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    diff.extend(new[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nx\nc\nd\n");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Added("x".into()),
+                DiffLine::Same("c".into()),
+                DiffLine::Added("d".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stale_error_display() {
+        let old: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+        let new = old.replace("line 10\n", "line ten\n");
+        let err = CheckError::Stale {
+            path: PathBuf::from("schemas.ts"),
+            diff: diff_lines(&old, &new),
+        };
+        let message = err.to_string();
+        assert!(
+            message.starts_with("schemas.ts is out of date"),
+            "{message}"
+        );
+        assert!(message.contains("@@ -7 +7 @@"), "{message}");
+        assert!(message.contains("-line 10\n+line ten\n"), "{message}");
+        assert!(!message.contains("line 1\n"), "{message}");
+        assert!(!message.contains("line 14"), "{message}");
+    }
+}
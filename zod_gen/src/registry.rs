@@ -0,0 +1,54 @@
+//! Link-time registry of types deriving `ZodSchema` with `#[zod(export)]`.
+//!
+//! Every exported type submits an [`ExportedSchema`] which
+//! [`ZodGenerator::from_registry`](crate::ZodGenerator::from_registry) collects,
+//! across all crates linked into the binary.
+
+use crate::ZodType;
+
+/// A schema registered with `#[zod(export)]`.
+#[derive(Debug)]
+pub struct ExportedSchema {
+    name: &'static str,
+    schema: fn() -> ZodType,
+    input_schema: fn() -> ZodType,
+}
+
+impl ExportedSchema {
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        schema: fn() -> ZodType,
+        input_schema: fn() -> ZodType,
+    ) -> Self {
+        Self {
+            name,
+            schema,
+            input_schema,
+        }
+    }
+
+    /// The export name: the Rust type name, or the name given with
+    /// `#[zod(rename = "...")]`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The schema of the exported type.
+    pub fn schema(&self) -> ZodType {
+        (self.schema)()
+    }
+
+    /// The input schema of the exported type, see
+    /// [`ZodSchema::zod_input_type`](crate::ZodSchema::zod_input_type).
+    pub fn input_schema(&self) -> ZodType {
+        (self.input_schema)()
+    }
+}
+
+inventory::collect!(ExportedSchema);
+
+/// Iterates over every schema exported with `#[zod(export)]`.
+pub fn exported_schemas() -> impl Iterator<Item = &'static ExportedSchema> {
+    inventory::iter::<ExportedSchema>.into_iter()
+}
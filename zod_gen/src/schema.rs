@@ -0,0 +1,641 @@
+//! Structured representation of the schemas produced by [`ZodSchema`](crate::ZodSchema).
+//!
+//! [`ZodType`] mirrors the Zod expressions built by the string helpers in the
+//! crate root. Keeping the structure around lets the same schema information
+//! be rendered as Zod, as plain TypeScript declarations, and so on.
+
+use serde_json::{Map, Value};
+
+use crate::validate::{self, ZodIssue};
+use crate::{
+    zod_array, zod_bigint, zod_boolean, zod_brand, zod_catchall, zod_default,
+    zod_discriminated_union, zod_enum, zod_intersection, zod_literal, zod_meta, zod_null,
+    zod_nullable, zod_number, zod_numeric_enum, zod_object, zod_optional, zod_record, zod_string,
+    zod_string_except, zod_tuple, zod_union,
+};
+
+/// A structured description of a Zod schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZodType {
+    /// `z.string()`
+    String,
+    /// `z.number()`
+    Number,
+    /// `z.bigint()`
+    BigInt,
+    /// `z.boolean()`
+    Boolean,
+    /// `z.null()`
+    Null,
+    /// `z.any()`
+    Any,
+    /// `z.literal('...')`
+    Literal(String),
+    /// `z.string().refine(...)` accepting any string but the given ones, e.g.
+    /// the unknown tags an enum's `#[serde(other)]` variant falls back to.
+    StringExcept(Vec<String>),
+    /// `z.enum([...])` of string values, as produced for enums with only
+    /// unit variants.
+    Enum(Vec<ZodEnumVariant>),
+    /// `z.union([z.literal(0), ...])` of integer values, as produced for enums
+    /// serialized by their discriminant, e.g. with `serde_repr`.
+    NumericEnum(Vec<ZodNumericEnumVariant>),
+    /// `<inner>.nullable()`
+    Nullable(Box<ZodType>),
+    /// `<inner>.optional()`
+    Optional(Box<ZodType>),
+    /// `z.array(<inner>)`
+    Array(Box<ZodType>),
+    /// `z.record(z.string(), <value>)`
+    Record(Box<ZodType>),
+    /// `z.object({ ... })`
+    Object(Vec<ZodField>),
+    /// `<object>.catchall(<rest>)`: an object whose other keys all match `rest`,
+    /// e.g. a struct flattening a map.
+    Catchall(Box<ZodType>, Box<ZodType>),
+    /// `<inner>.default(<value>)`: the field may be missing from the input,
+    /// and parsing fills in `value`, as serde does with `#[serde(default)]`.
+    Default(Box<ZodType>, Value),
+    /// `<inner>.meta({...})`: metadata like a title or examples, as used by
+    /// Zod 4's JSON Schema conversion and registries.
+    Meta(Box<ZodType>, Map<String, Value>),
+    /// `<inner>.brand<"<brand>">()`: a nominal type, so that e.g. two ID
+    /// newtypes wrapping a string can't be mixed up.
+    Brand(Box<ZodType>, String),
+    /// `z.tuple([...])`
+    Tuple(Vec<ZodType>),
+    /// `z.union([...])`
+    Union(Vec<ZodType>),
+    /// `z.discriminatedUnion('<tag>', [...])`
+    DiscriminatedUnion { tag: String, variants: Vec<ZodType> },
+    /// `z.intersection(<left>, <right>)`
+    Intersection(Box<ZodType>, Box<ZodType>),
+    /// A named Rust type, as produced by `#[derive(ZodSchema)]`.
+    ///
+    /// `id` is the module-qualified Rust path of the type. Renderers use it to
+    /// refer to a registered type by its export name instead of inlining it.
+    /// `name` is the export name used when the type is registered as a
+    /// dependency of another schema. `deprecated` is the note of a
+    /// `#[deprecated]` type: `None` if not deprecated, `Some("")` for a bare
+    /// `#[deprecated]`. Exports of a deprecated type get a `@deprecated`
+    /// JSDoc tag.
+    Named {
+        id: String,
+        name: String,
+        schema: Box<ZodType>,
+        deprecated: Option<String>,
+    },
+    /// A hand-written Zod expression with no known structure.
+    ///
+    /// This is what manual [`ZodSchema`](crate::ZodSchema) impls that only
+    /// provide `zod_schema()` produce.
+    Raw(String),
+}
+
+/// A single field of a [`ZodType::Object`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZodField {
+    pub name: String,
+    pub schema: ZodType,
+    /// Other keys the field may be given under, as with `#[serde(alias)]`.
+    pub aliases: Vec<String>,
+    /// The note of a `#[deprecated]` field: `None` if not deprecated,
+    /// `Some("")` for a bare `#[deprecated]`. Rendered as a `@deprecated`
+    /// JSDoc tag on the member.
+    pub deprecated: Option<String>,
+}
+
+/// A single value of a [`ZodType::Enum`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZodEnumVariant {
+    /// The Rust variant name, used as key of the generated constant object.
+    pub name: String,
+    /// The serialized value.
+    pub value: String,
+    /// Other values accepted for the variant, as with `#[serde(alias)]`.
+    pub aliases: Vec<String>,
+    /// The note of a `#[deprecated]` variant: `None` if not deprecated,
+    /// `Some("")` for a bare `#[deprecated]`. Rendered as a `@deprecated`
+    /// JSDoc tag in the enum's constant object.
+    pub deprecated: Option<String>,
+}
+
+impl ZodEnumVariant {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            aliases: Vec::new(),
+            deprecated: None,
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: &[&str]) -> Self {
+        self.aliases = aliases.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    pub fn with_deprecation(mut self, note: impl Into<String>) -> Self {
+        self.deprecated = Some(note.into());
+        self
+    }
+
+    /// The value followed by the aliases.
+    pub(crate) fn values(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.value.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+/// A single value of a [`ZodType::NumericEnum`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZodNumericEnumVariant {
+    /// The Rust variant name, used as key of the generated constant object.
+    pub name: String,
+    /// The discriminant the variant is serialized as.
+    pub value: i64,
+    /// The note of a `#[deprecated]` variant: `None` if not deprecated,
+    /// `Some("")` for a bare `#[deprecated]`.
+    pub deprecated: Option<String>,
+}
+
+impl ZodNumericEnumVariant {
+    pub fn new(name: impl Into<String>, value: i64) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            deprecated: None,
+        }
+    }
+
+    pub fn with_deprecation(mut self, note: impl Into<String>) -> Self {
+        self.deprecated = Some(note.into());
+        self
+    }
+}
+
+impl ZodField {
+    pub fn new(name: impl Into<String>, schema: ZodType) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            aliases: Vec::new(),
+            deprecated: None,
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: &[&str]) -> Self {
+        self.aliases = aliases.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    pub fn with_deprecation(mut self, note: impl Into<String>) -> Self {
+        self.deprecated = Some(note.into());
+        self
+    }
+}
+
+impl ZodType {
+    pub fn literal(value: impl Into<String>) -> Self {
+        ZodType::Literal(value.into())
+    }
+
+    pub fn string_except(values: Vec<String>) -> Self {
+        ZodType::StringExcept(values)
+    }
+
+    pub fn enumeration(variants: Vec<ZodEnumVariant>) -> Self {
+        ZodType::Enum(variants)
+    }
+
+    pub fn numeric_enumeration(variants: Vec<ZodNumericEnumVariant>) -> Self {
+        ZodType::NumericEnum(variants)
+    }
+
+    pub fn nullable(inner: ZodType) -> Self {
+        ZodType::Nullable(Box::new(inner))
+    }
+
+    pub fn optional(inner: ZodType) -> Self {
+        ZodType::Optional(Box::new(inner))
+    }
+
+    pub fn array(inner: ZodType) -> Self {
+        ZodType::Array(Box::new(inner))
+    }
+
+    pub fn record(value: ZodType) -> Self {
+        ZodType::Record(Box::new(value))
+    }
+
+    pub fn object(fields: Vec<ZodField>) -> Self {
+        ZodType::Object(fields)
+    }
+
+    pub fn catchall(object: ZodType, rest: ZodType) -> Self {
+        ZodType::Catchall(Box::new(object), Box::new(rest))
+    }
+
+    pub fn with_default(inner: ZodType, value: Value) -> Self {
+        ZodType::Default(Box::new(inner), value)
+    }
+
+    pub fn meta(inner: ZodType, meta: Map<String, Value>) -> Self {
+        ZodType::Meta(Box::new(inner), meta)
+    }
+
+    pub fn brand(inner: ZodType, brand: impl Into<String>) -> Self {
+        ZodType::Brand(Box::new(inner), brand.into())
+    }
+
+    pub fn discriminated_union(tag: impl Into<String>, variants: Vec<ZodType>) -> Self {
+        ZodType::DiscriminatedUnion {
+            tag: tag.into(),
+            variants,
+        }
+    }
+
+    pub fn intersection(left: ZodType, right: ZodType) -> Self {
+        ZodType::Intersection(Box::new(left), Box::new(right))
+    }
+
+    pub fn named(id: impl Into<String>, name: impl Into<String>, schema: ZodType) -> Self {
+        ZodType::Named {
+            id: id.into(),
+            name: name.into(),
+            schema: Box::new(schema),
+            deprecated: None,
+        }
+    }
+
+    /// Marks a [`ZodType::Named`] type as deprecated, with the note of its
+    /// `#[deprecated]` attribute. Other types are returned unchanged.
+    pub fn with_deprecation(self, note: impl Into<String>) -> Self {
+        match self {
+            ZodType::Named {
+                id, name, schema, ..
+            } => ZodType::Named {
+                id,
+                name,
+                schema,
+                deprecated: Some(note.into()),
+            },
+            other => other,
+        }
+    }
+
+    /// The deprecation note of a [`ZodType::Named`] type: `None` if not
+    /// deprecated, `Some("")` for a bare `#[deprecated]`.
+    pub(crate) fn deprecation(&self) -> Option<&str> {
+        match self {
+            ZodType::Named { deprecated, .. } => deprecated.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Checks `value` with the rules the rendered Zod schema applies when
+    /// parsing it, returning Zod's issues if it doesn't pass.
+    ///
+    /// Unknown object keys pass, as Zod strips them, and [`ZodType::Raw`]
+    /// expressions can't be checked, so they accept anything.
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use zod_gen::{ZodField, ZodType};
+    ///
+    /// let schema = ZodType::object(vec![ZodField::new("name", ZodType::String)]);
+    /// assert!(schema.validate(&json!({ "name": "Ada" })).is_ok());
+    ///
+    /// let issues = schema.validate(&json!({ "name": 1 })).unwrap_err();
+    /// assert_eq!(issues[0].to_string(), "Invalid input: expected string, received number at name");
+    /// ```
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<ZodIssue>> {
+        validate::validate(self, value)
+    }
+
+    /// Renders the schema as a Zod expression.
+    ///
+    /// Named types are always inlined, matching the output of the string
+    /// helpers in the crate root.
+    pub fn to_zod(&self) -> String {
+        self.to_zod_with(&mut |_| None)
+    }
+
+    /// Renders the schema as a Zod expression, resolving named types.
+    ///
+    /// `resolve` is called with the id of every nested [`ZodType::Named`]
+    /// type. Returning `Some(expr)` renders `expr` in its place, e.g. a
+    /// reference to an exported schema constant; `None` inlines the type.
+    pub(crate) fn to_zod_with(&self, resolve: &mut dyn FnMut(&str) -> Option<String>) -> String {
+        if let Some(aliases) = self.aliases_expr() {
+            let schema = self.without_aliases().to_zod_with(resolve);
+            return zod_rename_keys(&schema, &aliases);
+        }
+        let mut render_all = |items: &[ZodType]| -> Vec<String> {
+            items.iter().map(|i| i.to_zod_with(resolve)).collect()
+        };
+        match self {
+            ZodType::String => zod_string().to_string(),
+            ZodType::Number => zod_number().to_string(),
+            ZodType::BigInt => zod_bigint().to_string(),
+            ZodType::Boolean => zod_boolean().to_string(),
+            ZodType::Null => zod_null().to_string(),
+            ZodType::Any => "z.any()".to_string(),
+            ZodType::Literal(value) => zod_literal(value),
+            ZodType::StringExcept(values) => {
+                zod_string_except(&values.iter().map(String::as_str).collect::<Vec<_>>())
+            }
+            ZodType::Enum(variants) => zod_enum(
+                &variants
+                    .iter()
+                    .flat_map(ZodEnumVariant::values)
+                    .collect::<Vec<_>>(),
+            ),
+            ZodType::NumericEnum(variants) => {
+                zod_numeric_enum(&variants.iter().map(|v| v.value).collect::<Vec<_>>())
+            }
+            ZodType::Nullable(inner) => zod_nullable(&inner.to_zod_with(resolve)),
+            ZodType::Optional(inner) => zod_optional(&inner.to_zod_with(resolve)),
+            ZodType::Array(inner) => zod_array(&inner.to_zod_with(resolve)),
+            ZodType::Record(value) => zod_record(&value.to_zod_with(resolve)),
+            ZodType::Object(fields) => {
+                // The JSDoc tag goes on its own line above the key
+                let rendered: Vec<(String, String)> = fields
+                    .iter()
+                    .map(|f| {
+                        let key = property_key(&f.name);
+                        let key = match &f.deprecated {
+                            Some(note) => format!("{}\n  {key}", jsdoc_deprecated(note)),
+                            None => key,
+                        };
+                        (key, f.schema.to_zod_with(resolve))
+                    })
+                    .collect();
+                let refs: Vec<(&str, &str)> = rendered
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect();
+                zod_object(&refs)
+            }
+            ZodType::Catchall(object, rest) => {
+                zod_catchall(&object.to_zod_with(resolve), &rest.to_zod_with(resolve))
+            }
+            ZodType::Default(inner, value) => zod_default(&inner.to_zod_with(resolve), value),
+            ZodType::Meta(inner, meta) => zod_meta(&inner.to_zod_with(resolve), meta),
+            ZodType::Brand(inner, brand) => zod_brand(&inner.to_zod_with(resolve), brand),
+            ZodType::Tuple(items) => zod_tuple(&as_refs(&render_all(items))),
+            ZodType::Union(variants) => zod_union(&as_refs(&render_all(variants))),
+            ZodType::DiscriminatedUnion { tag, variants } => {
+                zod_discriminated_union(tag, &as_refs(&render_all(variants)))
+            }
+            ZodType::Intersection(left, right) => {
+                zod_intersection(&left.to_zod_with(resolve), &right.to_zod_with(resolve))
+            }
+            ZodType::Named { id, schema, .. } => {
+                resolve(id).unwrap_or_else(|| schema.to_zod_with(resolve))
+            }
+            ZodType::Raw(expr) => expr.clone(),
+        }
+    }
+
+    /// The JS expression of the `Map` from alias to field name applied to
+    /// `value` before parsing, since schemas can't express alternative keys:
+    /// an object's aliases, or those of the discriminated union variant
+    /// `value` is tagged as. Returns `None` if there are no aliases.
+    fn aliases_expr(&self) -> Option<String> {
+        match self {
+            ZodType::Object(_) | ZodType::Catchall(..) => {
+                let aliases = self.key_aliases();
+                (!aliases.is_empty()).then(|| js_map(&aliases))
+            }
+            // Keeps the union discriminated, as its members have to be objects
+            ZodType::DiscriminatedUnion { tag, variants } => {
+                let by_tag: Vec<(String, String)> = variants
+                    .iter()
+                    .flat_map(|variant| {
+                        let aliases = variant.shape().key_aliases();
+                        let tag_values = if aliases.is_empty() {
+                            Vec::new()
+                        } else {
+                            tag_values(variant.shape(), tag)
+                        };
+                        tag_values
+                            .into_iter()
+                            .map(move |value| (Value::from(value).to_string(), js_map(&aliases)))
+                    })
+                    .collect();
+                if by_tag.is_empty() {
+                    return None;
+                }
+                let entries: Vec<String> = by_tag
+                    .iter()
+                    .map(|(value, map)| format!("[{value}, {map}]"))
+                    .collect();
+                Some(format!(
+                    "new Map<string, Map<string, string>>([{}]).get((value as Record<string, unknown>)[{}] as string)",
+                    entries.join(", "),
+                    Value::from(tag.as_str())
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// The `(alias, name)` pairs of the fields of an object schema, looking
+    /// through names and metadata.
+    fn key_aliases(&self) -> Vec<(&str, &str)> {
+        match self.shape() {
+            ZodType::Object(fields) => fields
+                .iter()
+                .flat_map(|f| {
+                    f.aliases
+                        .iter()
+                        .map(|alias| (alias.as_str(), f.name.as_str()))
+                })
+                .collect(),
+            ZodType::Catchall(object, _) => object.key_aliases(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The schema with the aliases of its object, or of the objects of its
+    /// discriminated union, removed.
+    fn without_aliases(&self) -> ZodType {
+        match self {
+            ZodType::Object(fields) => ZodType::Object(
+                fields
+                    .iter()
+                    .map(|f| ZodField {
+                        aliases: Vec::new(),
+                        ..f.clone()
+                    })
+                    .collect(),
+            ),
+            ZodType::Catchall(object, rest) => {
+                ZodType::Catchall(Box::new(object.without_aliases()), rest.clone())
+            }
+            ZodType::DiscriminatedUnion { tag, variants } => ZodType::DiscriminatedUnion {
+                tag: tag.clone(),
+                variants: variants.iter().map(ZodType::without_aliases).collect(),
+            },
+            ZodType::Meta(inner, meta) => {
+                ZodType::Meta(Box::new(inner.without_aliases()), meta.clone())
+            }
+            ZodType::Named {
+                id,
+                name,
+                schema,
+                deprecated,
+            } => ZodType::Named {
+                id: id.clone(),
+                name: name.clone(),
+                schema: Box::new(schema.without_aliases()),
+                deprecated: deprecated.clone(),
+            },
+            other => other.clone(),
+        }
+    }
+
+    /// Calls `visit` with every [`ZodType::Named`] type nested in this
+    /// schema, without descending into the named types themselves.
+    pub(crate) fn visit_named(&self, visit: &mut dyn FnMut(&ZodType)) {
+        match self {
+            ZodType::String
+            | ZodType::Number
+            | ZodType::BigInt
+            | ZodType::Boolean
+            | ZodType::Null
+            | ZodType::Any
+            | ZodType::Literal(_)
+            | ZodType::StringExcept(_)
+            | ZodType::Enum(_)
+            | ZodType::NumericEnum(_)
+            | ZodType::Raw(_) => {}
+            ZodType::Nullable(inner)
+            | ZodType::Optional(inner)
+            | ZodType::Array(inner)
+            | ZodType::Record(inner)
+            | ZodType::Default(inner, _)
+            | ZodType::Meta(inner, _)
+            | ZodType::Brand(inner, _) => inner.visit_named(visit),
+            ZodType::Object(fields) => fields.iter().for_each(|f| f.schema.visit_named(visit)),
+            ZodType::Tuple(items)
+            | ZodType::Union(items)
+            | ZodType::DiscriminatedUnion {
+                variants: items, ..
+            } => items.iter().for_each(|i| i.visit_named(visit)),
+            ZodType::Intersection(left, right) | ZodType::Catchall(left, right) => {
+                left.visit_named(visit);
+                right.visit_named(visit);
+            }
+            ZodType::Named { .. } => visit(self),
+        }
+    }
+
+    /// The named types this schema refers to directly, i.e. without looking
+    /// into the named types themselves. Each is a [`ZodType::Named`].
+    ///
+    /// A named schema's own wrapper is skipped, so for a derived type this
+    /// returns the types of its fields and variants.
+    pub fn dependencies(&self) -> Vec<ZodType> {
+        let mut deps = Vec::new();
+        self.unnamed().visit_named(&mut |named| {
+            if !deps.contains(named) {
+                deps.push(named.clone());
+            }
+        });
+        deps
+    }
+
+    /// Returns the schema with its name and metadata removed, i.e. the
+    /// shape of its values.
+    pub(crate) fn shape(&self) -> &ZodType {
+        match self.unnamed() {
+            ZodType::Meta(inner, _) => inner,
+            other => other,
+        }
+    }
+
+    /// Returns the schema with its [`ZodType::Named`] wrapper removed.
+    ///
+    /// Only the outermost wrapper is removed: a named type delegating to
+    /// another one, e.g. with `#[serde(transparent)]`, refers to it.
+    pub fn unnamed(&self) -> &ZodType {
+        match self {
+            ZodType::Named { schema, .. } => schema,
+            other => other,
+        }
+    }
+}
+
+/// The values of the `tag` field of a discriminated union variant.
+fn tag_values(variant: &ZodType, tag: &str) -> Vec<String> {
+    let fields = match variant {
+        ZodType::Object(fields) => fields,
+        ZodType::Catchall(object, _) => return tag_values(object.shape(), tag),
+        _ => return Vec::new(),
+    };
+    match fields
+        .iter()
+        .find(|f| f.name == tag)
+        .map(|f| f.schema.shape())
+    {
+        Some(ZodType::Literal(value)) => vec![value.clone()],
+        Some(ZodType::Enum(variants)) => variants
+            .iter()
+            .flat_map(ZodEnumVariant::values)
+            .map(ToString::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Renders a JS `Map` of `(key, value)` string pairs.
+fn js_map(entries: &[(&str, &str)]) -> String {
+    let entries: Vec<String> = entries
+        .iter()
+        .map(|(key, value)| format!("[{}, {}]", Value::from(*key), Value::from(*value)))
+        .collect();
+    format!("new Map([{}])", entries.join(", "))
+}
+
+/// Renders `z.preprocess(...)` renaming the keys of an object found in the
+/// `Map` that `aliases` evaluates to before parsing it with `schema`.
+fn zod_rename_keys(schema: &str, aliases: &str) -> String {
+    format!(
+        "z.preprocess((value) => {{\n  \
+         if (typeof value !== 'object' || value === null || Array.isArray(value)) return value;\n  \
+         const aliases = {aliases};\n  \
+         return Object.fromEntries(Object.entries(value).map(([key, item]) => [aliases?.get(key) ?? key, item]));\n\
+         }}, {schema})"
+    )
+}
+
+fn as_refs(items: &[String]) -> Vec<&str> {
+    items.iter().map(String::as_str).collect()
+}
+
+/// Renders an object key, JSON-quoted unless it is a valid identifier, e.g.
+/// `name` but `"content-type"`.
+pub(crate) fn property_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        Value::from(name).to_string()
+    }
+}
+
+/// Renders the `/** @deprecated <note> */` JSDoc comment of a deprecation
+/// note.
+pub(crate) fn jsdoc_deprecated(note: &str) -> String {
+    let note = note.replace("*/", "*\\/");
+    if note.is_empty() {
+        "/** @deprecated */".to_string()
+    } else {
+        format!("/** @deprecated {note} */")
+    }
+}
@@ -0,0 +1,108 @@
+//! Test helpers checking that serde and the generated schemas agree.
+//!
+//! [`assert_conforms`] serializes a value with `serde_json` and validates the
+//! JSON against the type's schema with
+//! [`ZodType::validate`](crate::ZodType::validate), so no JS runtime is
+//! needed. It panics with an explanation, which makes it usable in plain
+//! tests as well as in `proptest!` blocks with generated values:
+//!
+//! ```rust,ignore
+//! proptest! {
+//!     #[test]
+//!     fn orders_conform(order in any::<Order>()) {
+//!         zod_gen::testing::assert_conforms::<Order>(&order);
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{ZodIssue, ZodSchema};
+
+/// Error returned by [`check_conforms`].
+#[derive(Debug)]
+pub enum ConformanceError {
+    /// `serde_json` failed to serialize the value.
+    Serialize {
+        rust_type: &'static str,
+        source: serde_json::Error,
+    },
+    /// The schema rejects the serialized value.
+    Mismatch {
+        rust_type: &'static str,
+        json: Value,
+        schema: String,
+        issues: Vec<ZodIssue>,
+    },
+}
+
+impl fmt::Display for ConformanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConformanceError::Serialize { rust_type, source } => {
+                write!(f, "failed to serialize `{rust_type}` to JSON: {source}")
+            }
+            ConformanceError::Mismatch {
+                rust_type,
+                json,
+                schema,
+                issues,
+            } => {
+                writeln!(
+                    f,
+                    "the Zod schema of `{rust_type}` rejects its serialized JSON:"
+                )?;
+                for issue in issues {
+                    writeln!(f, "  - {issue}")?;
+                }
+                let json = serde_json::to_string_pretty(json).map_err(|_| fmt::Error)?;
+                writeln!(f, "JSON:\n{json}")?;
+                write!(f, "schema:\n{schema}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConformanceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConformanceError::Serialize { source, .. } => Some(source),
+            ConformanceError::Mismatch { .. } => None,
+        }
+    }
+}
+
+/// Serializes `value` with `serde_json` and validates the JSON against
+/// `T`'s schema, i.e. [`ZodSchema::zod_type`].
+pub fn check_conforms<T: Serialize + ZodSchema + ?Sized>(
+    value: &T,
+) -> Result<(), ConformanceError> {
+    let rust_type = std::any::type_name::<T>();
+    let json = serde_json::to_value(value)
+        .map_err(|source| ConformanceError::Serialize { rust_type, source })?;
+    let schema = T::zod_type();
+    schema
+        .validate(&json)
+        .map_err(|issues| ConformanceError::Mismatch {
+            rust_type,
+            json,
+            schema: schema.to_zod(),
+            issues,
+        })
+}
+
+/// Asserts that `value` serializes to JSON `T`'s schema accepts.
+///
+/// # Panics
+///
+/// If serializing fails or the schema rejects the JSON, with the issues, the
+/// JSON and the schema.
+#[track_caller]
+pub fn assert_conforms<T: Serialize + ZodSchema + ?Sized>(value: &T) {
+    if let Err(err) = check_conforms(value) {
+        panic!("{err}");
+    }
+}
@@ -0,0 +1,286 @@
+//! Plain TypeScript declarations rendered from [`ZodType`].
+//!
+//! The output describes the same values as `z.infer<typeof XSchema>` would,
+//! without depending on Zod.
+
+use std::collections::BTreeMap;
+
+use crate::schema::{jsdoc_deprecated, property_key};
+use crate::{ZodEnumVariant, ZodField, ZodType};
+
+/// Renders `schema` as a TypeScript type expression.
+///
+/// `names` maps the id of a [`ZodType::Named`] type to the export name it was
+/// registered under. Registered types are referenced by that name, everything
+/// else is inlined.
+pub(crate) fn render_type(schema: &ZodType, names: &BTreeMap<String, String>) -> String {
+    match schema {
+        // TypeScript can't exclude values from `string`
+        ZodType::String | ZodType::StringExcept(_) => "string".to_string(),
+        ZodType::Number => "number".to_string(),
+        ZodType::BigInt => "bigint".to_string(),
+        ZodType::Boolean => "boolean".to_string(),
+        ZodType::Null => "null".to_string(),
+        ZodType::Any => "any".to_string(),
+        // Hand-written Zod expressions carry no structure we could translate.
+        ZodType::Raw(_) => "unknown".to_string(),
+        ZodType::Literal(value) => format!("'{value}'"),
+        ZodType::Enum(variants) => {
+            let values: Vec<String> = variants
+                .iter()
+                .flat_map(ZodEnumVariant::values)
+                .map(|value| format!("'{value}'"))
+                .collect();
+            values.join(" | ")
+        }
+        ZodType::NumericEnum(variants) if variants.is_empty() => "never".to_string(),
+        ZodType::NumericEnum(variants) => {
+            let values: Vec<String> = variants.iter().map(|v| v.value.to_string()).collect();
+            values.join(" | ")
+        }
+        ZodType::Nullable(inner) => format!("{} | null", render_type(inner, names)),
+        ZodType::Optional(inner) => format!("{} | undefined", render_type(inner, names)),
+        ZodType::Array(inner) => {
+            let item = render_type(inner, names);
+            if needs_parens(inner) {
+                format!("({item})[]")
+            } else {
+                format!("{item}[]")
+            }
+        }
+        ZodType::Record(value) => format!("Record<string, {}>", render_type(value, names)),
+        ZodType::Object(fields) => {
+            if fields.is_empty() {
+                return "{}".to_string();
+            }
+            let members: Vec<String> = fields
+                .iter()
+                .map(|f| match &f.deprecated {
+                    Some(note) => format!("{} {}", jsdoc_deprecated(note), render_member(f, names)),
+                    None => render_member(f, names),
+                })
+                .collect();
+            format!("{{ {} }}", members.join("; "))
+        }
+        // An index signature would have to admit the known fields' types too
+        ZodType::Catchall(object, rest) => format!(
+            "{} & Record<string, {}>",
+            render_type(object, names),
+            render_type(rest, names)
+        ),
+        ZodType::Meta(inner, _) => render_type(inner, names),
+        // Parsing fills in a missing field, so `z.infer` keeps it required
+        ZodType::Default(inner, _) => render_type(inner, names),
+        ZodType::Brand(inner, brand) => {
+            let rendered = render_type(inner, names);
+            let inner = if needs_parens(inner) {
+                format!("({rendered})")
+            } else {
+                rendered
+            };
+            format!("{inner} & {{ readonly __brand: '{brand}' }}")
+        }
+        ZodType::Tuple(items) => {
+            let items: Vec<String> = items.iter().map(|i| render_type(i, names)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        ZodType::Union(variants) | ZodType::DiscriminatedUnion { variants, .. } => {
+            let variants: Vec<String> = variants.iter().map(|v| render_type(v, names)).collect();
+            variants.join(" | ")
+        }
+        ZodType::Intersection(left, right) => {
+            let wrap = |t: &ZodType| {
+                let rendered = render_type(t, names);
+                if needs_parens(t) {
+                    format!("({rendered})")
+                } else {
+                    rendered
+                }
+            };
+            format!("{} & {}", wrap(left), wrap(right))
+        }
+        ZodType::Named { id, schema, .. } => match names.get(id) {
+            Some(export_name) => export_name.clone(),
+            None => render_type(schema, names),
+        },
+    }
+}
+
+/// Renders an exported declaration for a registered schema.
+///
+/// Object schemas become interfaces, everything else a type alias.
+pub(crate) fn render_declaration(
+    name: &str,
+    schema: &ZodType,
+    names: &BTreeMap<String, String>,
+) -> String {
+    let declaration = match schema.shape() {
+        ZodType::Object(fields) => {
+            let members: String = fields
+                .iter()
+                .map(|f| {
+                    let doc = match &f.deprecated {
+                        Some(note) => format!("  {}\n", jsdoc_deprecated(note)),
+                        None => String::new(),
+                    };
+                    format!("{doc}  {};\n", render_member(f, names))
+                })
+                .collect();
+            format!("export interface {name} {{\n{members}}}\n")
+        }
+        other => format!("export type {name} = {};\n", render_type(other, names)),
+    };
+    match schema.deprecation() {
+        Some(note) => format!("{}\n{declaration}", jsdoc_deprecated(note)),
+        None => declaration,
+    }
+}
+
+fn render_member(field: &ZodField, names: &BTreeMap<String, String>) -> String {
+    let name = property_key(&field.name);
+    match &field.schema {
+        ZodType::Optional(inner) => format!("{name}?: {}", render_type(inner, names)),
+        other => format!("{name}: {}", render_type(other, names)),
+    }
+}
+
+/// Whether the rendered type has to be parenthesized inside `T[]` or `A & B`.
+fn needs_parens(schema: &ZodType) -> bool {
+    match schema {
+        ZodType::Nullable(_)
+        | ZodType::Optional(_)
+        | ZodType::Union(_)
+        | ZodType::Enum(_)
+        | ZodType::NumericEnum(_)
+        | ZodType::DiscriminatedUnion { .. }
+        | ZodType::Intersection(..)
+        | ZodType::Catchall(..)
+        | ZodType::Brand(..) => true,
+        ZodType::Named { schema, .. } | ZodType::Default(schema, _) | ZodType::Meta(schema, _) => {
+            needs_parens(schema)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_names() -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+
+    #[test]
+    fn test_render_primitives_and_wrappers() {
+        let names = no_names();
+        assert_eq!(render_type(&ZodType::String, &names), "string");
+        assert_eq!(
+            render_type(&ZodType::nullable(ZodType::Number), &names),
+            "number | null"
+        );
+        assert_eq!(
+            render_type(&ZodType::array(ZodType::nullable(ZodType::String)), &names),
+            "(string | null)[]"
+        );
+        assert_eq!(
+            render_type(&ZodType::record(ZodType::Boolean), &names),
+            "Record<string, boolean>"
+        );
+        assert_eq!(
+            render_type(
+                &ZodType::Tuple(vec![ZodType::Number, ZodType::String]),
+                &names
+            ),
+            "[number, string]"
+        );
+        assert_eq!(
+            render_type(&ZodType::Raw("z.string().email()".into()), &names),
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn test_render_union_of_literals() {
+        let schema = ZodType::Union(vec![ZodType::literal("a"), ZodType::literal("b")]);
+        assert_eq!(render_type(&schema, &no_names()), "'a' | 'b'");
+    }
+
+    #[test]
+    fn test_render_declaration_interface() {
+        let schema = ZodType::object(vec![
+            ZodField::new("id", ZodType::Number),
+            ZodField::new("email", ZodType::nullable(ZodType::String)),
+            ZodField::new("nickname", ZodType::optional(ZodType::String)),
+        ]);
+        assert_eq!(
+            render_declaration("User", &schema, &no_names()),
+            "export interface User {\n  id: number;\n  email: string | null;\n  nickname?: string;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_defaulted_member() {
+        let schema = ZodType::object(vec![ZodField::new(
+            "per_page",
+            ZodType::with_default(ZodType::Number, serde_json::json!(20)),
+        )]);
+        assert_eq!(
+            render_declaration("Paging", &schema, &no_names()),
+            "export interface Paging {\n  per_page: number;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_quoted_keys() {
+        let schema = ZodType::object(vec![
+            ZodField::new("content-type", ZodType::String),
+            ZodField::new("$ref", ZodType::optional(ZodType::String)),
+            ZodField::new("2fa", ZodType::Boolean),
+        ]);
+        assert_eq!(
+            render_type(&schema, &no_names()),
+            "{ \"content-type\": string; $ref?: string; \"2fa\": boolean }"
+        );
+        assert_eq!(
+            render_declaration("Headers", &schema, &no_names()),
+            "export interface Headers {\n  \"content-type\": string;\n  $ref?: string;\n  \"2fa\": boolean;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_deprecated_members() {
+        let schema = ZodType::object(vec![
+            ZodField::new("id", ZodType::Number).with_deprecation("use uuid"),
+            ZodField::new("uuid", ZodType::String),
+        ]);
+        assert_eq!(
+            render_type(&schema, &no_names()),
+            "{ /** @deprecated use uuid */ id: number; uuid: string }"
+        );
+        let named = ZodType::named("app::User", "User", schema).with_deprecation("");
+        assert_eq!(
+            render_declaration("User", &named, &no_names()),
+            "/** @deprecated */\nexport interface User {\n  /** @deprecated use uuid */\n  id: number;\n  uuid: string;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_named_reference() {
+        let address = ZodType::named(
+            "app::Address",
+            "Address",
+            ZodType::object(vec![ZodField::new("city", ZodType::String)]),
+        );
+        let schema = ZodType::object(vec![ZodField::new("address", address)]);
+
+        let mut names = no_names();
+        assert_eq!(
+            render_type(&schema, &names),
+            "{ address: { city: string } }"
+        );
+
+        names.insert("app::Address".into(), "Address".into());
+        assert_eq!(render_type(&schema, &names), "{ address: Address }");
+    }
+}
@@ -0,0 +1,669 @@
+//! Checking JSON values against a [`ZodType`] in Rust.
+//!
+//! The rules are the ones the generated Zod schema applies when parsing, and
+//! failures are reported as issues shaped like Zod's, with the same codes,
+//! paths and default messages.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde_json::{json, Map, Value};
+
+use crate::{ZodEnumVariant, ZodField, ZodType};
+
+/// A single problem found by [`ZodType::validate`], like a Zod issue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZodIssue {
+    pub code: ZodIssueCode,
+    /// Where in the value the problem is.
+    pub path: Vec<PathSegment>,
+    /// Zod's default message for the issue.
+    pub message: String,
+}
+
+/// The kind of a [`ZodIssue`], with the details Zod reports for it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZodIssueCode {
+    /// `invalid_type`: the value, or a missing field, is not of the
+    /// `expected` type.
+    InvalidType { expected: &'static str },
+    /// `invalid_value`: the value is none of the allowed literal `values`.
+    InvalidValue { values: Vec<Value> },
+    /// `invalid_union`: no member of a union matched. `errors` holds the
+    /// issues of each member; a discriminated union whose tag matches no
+    /// member reports none and the `note` "No matching discriminator".
+    InvalidUnion {
+        errors: Vec<Vec<ZodIssue>>,
+        note: Option<&'static str>,
+    },
+    /// `too_big`: a tuple has more than `maximum` items.
+    TooBig { maximum: usize },
+    /// `too_small`: a tuple has fewer than `minimum` items.
+    TooSmall { minimum: usize },
+    /// `custom`: a `.refine(...)` check failed, e.g. a string is one of the
+    /// values a [`ZodType::StringExcept`] excludes.
+    Custom,
+}
+
+/// One step of a [`ZodIssue`] path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl ZodIssueCode {
+    /// The issue code as Zod names it, e.g. `"invalid_type"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ZodIssueCode::InvalidType { .. } => "invalid_type",
+            ZodIssueCode::InvalidValue { .. } => "invalid_value",
+            ZodIssueCode::InvalidUnion { .. } => "invalid_union",
+            ZodIssueCode::TooBig { .. } => "too_big",
+            ZodIssueCode::TooSmall { .. } => "too_small",
+            ZodIssueCode::Custom => "custom",
+        }
+    }
+}
+
+impl ZodIssue {
+    /// The issue as the JSON object Zod would report, e.g.
+    /// `{ "code": "invalid_type", "expected": "string", "path": ["name"], "message": "..." }`.
+    pub fn to_json(&self) -> Value {
+        let mut issue = Map::new();
+        issue.insert("code".to_string(), json!(self.code.as_str()));
+        match &self.code {
+            ZodIssueCode::InvalidType { expected } => {
+                issue.insert("expected".to_string(), json!(expected));
+            }
+            ZodIssueCode::InvalidValue { values } => {
+                issue.insert("values".to_string(), json!(values));
+            }
+            ZodIssueCode::InvalidUnion { errors, note } => {
+                let errors: Vec<Vec<Value>> = errors
+                    .iter()
+                    .map(|issues| issues.iter().map(ZodIssue::to_json).collect())
+                    .collect();
+                issue.insert("errors".to_string(), json!(errors));
+                if let Some(note) = note {
+                    issue.insert("note".to_string(), json!(note));
+                }
+            }
+            ZodIssueCode::TooBig { maximum } => {
+                issue.insert("origin".to_string(), json!("array"));
+                issue.insert("maximum".to_string(), json!(maximum));
+                issue.insert("inclusive".to_string(), json!(true));
+            }
+            ZodIssueCode::TooSmall { minimum } => {
+                issue.insert("origin".to_string(), json!("array"));
+                issue.insert("minimum".to_string(), json!(minimum));
+                issue.insert("inclusive".to_string(), json!(true));
+            }
+            ZodIssueCode::Custom => {}
+        }
+        let path: Vec<Value> = self
+            .path
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Key(key) => json!(key),
+                PathSegment::Index(index) => json!(index),
+            })
+            .collect();
+        issue.insert("path".to_string(), json!(path));
+        issue.insert("message".to_string(), json!(self.message));
+        Value::Object(issue)
+    }
+}
+
+/// `message at path`, with the path written like `items[0].name`.
+impl fmt::Display for ZodIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+        if self.path.is_empty() {
+            return Ok(());
+        }
+        f.write_str(" at ")?;
+        for (i, segment) in self.path.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if i == 0 => f.write_str(key)?,
+                PathSegment::Key(key) => write!(f, ".{key}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ZodIssue {}
+
+/// Walks a value along with its schema, collecting issues.
+struct Validator {
+    path: Vec<PathSegment>,
+    issues: Vec<ZodIssue>,
+}
+
+impl Validator {
+    fn issue(&mut self, code: ZodIssueCode) {
+        let message = message(&code, None);
+        self.issues.push(ZodIssue {
+            code,
+            path: self.path.clone(),
+            message,
+        });
+    }
+
+    fn invalid_type(&mut self, expected: &'static str, value: Option<&Value>) {
+        let code = ZodIssueCode::InvalidType { expected };
+        let message = message(&code, Some(received(value)));
+        self.issues.push(ZodIssue {
+            code,
+            path: self.path.clone(),
+            message,
+        });
+    }
+
+    fn at(&mut self, segment: PathSegment, f: impl FnOnce(&mut Self)) {
+        self.path.push(segment);
+        f(self);
+        self.path.pop();
+    }
+
+    /// Validates `value`, `None` standing for a missing field, i.e.
+    /// `undefined`.
+    fn check(&mut self, schema: &ZodType, value: Option<&Value>) {
+        // Hand-written Zod expressions can't be checked here
+        match (schema, value) {
+            (ZodType::String, Some(Value::String(_)))
+            | (ZodType::Number, Some(Value::Number(_)))
+            | (ZodType::Boolean, Some(Value::Bool(_)))
+            | (ZodType::Null, Some(Value::Null))
+            | (ZodType::Any | ZodType::Raw(_), _)
+            | (ZodType::Optional(_) | ZodType::Default(..), None) => {}
+            (ZodType::String, _) => self.invalid_type("string", value),
+            (ZodType::Number, _) => self.invalid_type("number", value),
+            // JSON has no bigints
+            (ZodType::BigInt, _) => self.invalid_type("bigint", value),
+            (ZodType::Boolean, _) => self.invalid_type("boolean", value),
+            (ZodType::Null, _) => self.invalid_type("null", value),
+            (ZodType::Literal(literal), _) => {
+                if value.and_then(Value::as_str) != Some(literal.as_str()) {
+                    self.issue(ZodIssueCode::InvalidValue {
+                        values: vec![json!(literal)],
+                    });
+                }
+            }
+            (ZodType::StringExcept(values), Some(Value::String(v))) => {
+                if values.contains(v) {
+                    self.issue(ZodIssueCode::Custom);
+                }
+            }
+            (ZodType::StringExcept(_), _) => self.invalid_type("string", value),
+            (ZodType::Enum(variants), _) => {
+                let values: Vec<&str> = variants.iter().flat_map(ZodEnumVariant::values).collect();
+                if !value
+                    .and_then(Value::as_str)
+                    .is_some_and(|v| values.contains(&v))
+                {
+                    let values = values.into_iter().map(|v| json!(v)).collect();
+                    self.issue(ZodIssueCode::InvalidValue { values });
+                }
+            }
+            // Rendered as `z.literal(...)`, a union of them or `z.never()`
+            (ZodType::NumericEnum(variants), _) => match variants.as_slice() {
+                [] => self.invalid_type("never", value),
+                [variant] => self.numeric_literal(variant.value, value),
+                _ => self.union(
+                    value,
+                    variants.iter().map(|v| {
+                        move |this: &mut Self, value: Option<&Value>| {
+                            this.numeric_literal(v.value, value)
+                        }
+                    }),
+                ),
+            },
+            (ZodType::Nullable(_), Some(Value::Null)) => {}
+            (ZodType::Nullable(inner) | ZodType::Optional(inner), _)
+            | (
+                ZodType::Default(inner, _) | ZodType::Meta(inner, _) | ZodType::Brand(inner, _),
+                _,
+            )
+            | (ZodType::Named { schema: inner, .. }, _) => self.check(inner, value),
+            (ZodType::Array(inner), Some(Value::Array(items))) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.at(PathSegment::Index(i), |this| this.check(inner, Some(item)));
+                }
+            }
+            (ZodType::Array(_), _) => self.invalid_type("array", value),
+            (ZodType::Record(inner), Some(Value::Object(map))) => {
+                for (key, item) in map {
+                    self.at(PathSegment::Key(key.clone()), |this| {
+                        this.check(inner, Some(item))
+                    });
+                }
+            }
+            (ZodType::Record(_), _) => self.invalid_type("record", value),
+            (ZodType::Object(fields), Some(Value::Object(map))) => {
+                self.fields(fields, &rename_aliases(fields, map))
+            }
+            (ZodType::Catchall(object, rest), Some(Value::Object(map))) => {
+                self.check(object, value);
+                let known = object_fields(object);
+                for (key, item) in rename_aliases(known, map).iter() {
+                    if !known.iter().any(|f| &f.name == key) {
+                        self.at(PathSegment::Key(key.clone()), |this| {
+                            this.check(rest, Some(item))
+                        });
+                    }
+                }
+            }
+            (ZodType::Object(_) | ZodType::Catchall(..), _) => self.invalid_type("object", value),
+            (ZodType::Tuple(items), Some(Value::Array(values))) => {
+                if values.len() > items.len() {
+                    self.issue(ZodIssueCode::TooBig {
+                        maximum: items.len(),
+                    });
+                    return;
+                }
+                // Trailing optional items may be left out
+                let required = items
+                    .iter()
+                    .rposition(|item| !accepts_missing(item))
+                    .map_or(0, |i| i + 1);
+                if values.len() < required {
+                    self.issue(ZodIssueCode::TooSmall { minimum: required });
+                    return;
+                }
+                for (i, item) in items.iter().enumerate() {
+                    self.at(PathSegment::Index(i), |this| {
+                        this.check(item, values.get(i))
+                    });
+                }
+            }
+            (ZodType::Tuple(_), _) => self.invalid_type("tuple", value),
+            (ZodType::Union(members), _) => self.union(
+                value,
+                members
+                    .iter()
+                    .map(|m| move |this: &mut Self, value: Option<&Value>| this.check(m, value)),
+            ),
+            (ZodType::DiscriminatedUnion { tag, variants }, Some(Value::Object(map))) => {
+                let matching = map.get(tag).and_then(|tag_value| {
+                    variants.iter().find(|variant| {
+                        object_fields(variant.shape())
+                            .iter()
+                            .find(|f| &f.name == tag)
+                            .is_some_and(|f| accepts(&f.schema, tag_value))
+                    })
+                });
+                match matching {
+                    Some(variant) => self.check(variant, value),
+                    None => self.at(PathSegment::Key(tag.clone()), |this| {
+                        this.issue(ZodIssueCode::InvalidUnion {
+                            errors: Vec::new(),
+                            note: Some("No matching discriminator"),
+                        })
+                    }),
+                }
+            }
+            (ZodType::DiscriminatedUnion { .. }, _) => self.invalid_type("object", value),
+            (ZodType::Intersection(left, right), _) => {
+                self.check(left, value);
+                self.check(right, value);
+            }
+        }
+    }
+
+    fn numeric_literal(&mut self, literal: i64, value: Option<&Value>) {
+        if value.and_then(Value::as_i64) != Some(literal) {
+            self.issue(ZodIssueCode::InvalidValue {
+                values: vec![json!(literal)],
+            });
+        }
+    }
+
+    /// Checks the known fields of an object. Other keys are stripped by Zod,
+    /// so they are fine.
+    fn fields(&mut self, fields: &[ZodField], map: &Map<String, Value>) {
+        for field in fields {
+            self.at(PathSegment::Key(field.name.clone()), |this| {
+                this.check(&field.schema, map.get(&field.name))
+            });
+        }
+    }
+
+    /// Passes if any member does, otherwise reports every member's issues.
+    fn union<F: Fn(&mut Self, Option<&Value>)>(
+        &mut self,
+        value: Option<&Value>,
+        members: impl Iterator<Item = F>,
+    ) {
+        let mut errors = Vec::new();
+        for member in members {
+            let mut validator = Validator {
+                path: self.path.clone(),
+                issues: Vec::new(),
+            };
+            member(&mut validator, value);
+            if validator.issues.is_empty() {
+                return;
+            }
+            errors.push(validator.issues);
+        }
+        self.issue(ZodIssueCode::InvalidUnion { errors, note: None });
+    }
+}
+
+/// Validates `value` against `schema`, see [`ZodType::validate`].
+pub(crate) fn validate(schema: &ZodType, value: &Value) -> Result<(), Vec<ZodIssue>> {
+    let mut validator = Validator {
+        path: Vec::new(),
+        issues: Vec::new(),
+    };
+    validator.check(schema, Some(value));
+    if validator.issues.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.issues)
+    }
+}
+
+fn accepts(schema: &ZodType, value: &Value) -> bool {
+    validate(schema, value).is_ok()
+}
+
+/// Whether a missing value passes, like an optional field.
+fn accepts_missing(schema: &ZodType) -> bool {
+    let mut validator = Validator {
+        path: Vec::new(),
+        issues: Vec::new(),
+    };
+    validator.check(schema, None);
+    validator.issues.is_empty()
+}
+
+/// The object with the keys that are aliases of `fields` renamed to the
+/// field's name, like the `z.preprocess` rendered for them does.
+fn rename_aliases<'a>(
+    fields: &[ZodField],
+    map: &'a Map<String, Value>,
+) -> Cow<'a, Map<String, Value>> {
+    let mut map = Cow::Borrowed(map);
+    for field in fields {
+        for alias in &field.aliases {
+            if map.contains_key(alias) && !map.contains_key(&field.name) {
+                let map = map.to_mut();
+                let item = map.remove(alias).unwrap();
+                map.insert(field.name.clone(), item);
+            }
+        }
+    }
+    map
+}
+
+/// The fields of an object schema, looking through names and metadata.
+fn object_fields(schema: &ZodType) -> &[ZodField] {
+    match schema.shape() {
+        ZodType::Object(fields) => fields,
+        ZodType::Catchall(object, _) => object_fields(object),
+        _ => &[],
+    }
+}
+
+/// The type of a value as Zod names it in messages.
+fn received(value: Option<&Value>) -> &'static str {
+    match value {
+        None => "undefined",
+        Some(Value::Null) => "null",
+        Some(Value::Bool(_)) => "boolean",
+        Some(Value::Number(_)) => "number",
+        Some(Value::String(_)) => "string",
+        Some(Value::Array(_)) => "array",
+        Some(Value::Object(_)) => "object",
+    }
+}
+
+/// Zod's default English message for an issue.
+fn message(code: &ZodIssueCode, received: Option<&str>) -> String {
+    match code {
+        ZodIssueCode::InvalidType { expected } => format!(
+            "Invalid input: expected {expected}, received {}",
+            received.unwrap_or("undefined")
+        ),
+        ZodIssueCode::InvalidValue { values } => match values.as_slice() {
+            [value] => format!("Invalid input: expected {value}"),
+            _ => {
+                let values: Vec<String> = values.iter().map(Value::to_string).collect();
+                format!("Invalid option: expected one of {}", values.join("|"))
+            }
+        },
+        ZodIssueCode::InvalidUnion { .. } | ZodIssueCode::Custom => "Invalid input".to_string(),
+        ZodIssueCode::TooBig { maximum } => {
+            format!("Too big: expected array to have <={maximum} items")
+        }
+        ZodIssueCode::TooSmall { minimum } => {
+            format!("Too small: expected array to have >={minimum} items")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ZodNumericEnumVariant;
+
+    fn issues(schema: &ZodType, value: Value) -> Vec<Value> {
+        schema
+            .validate(&value)
+            .unwrap_err()
+            .iter()
+            .map(ZodIssue::to_json)
+            .collect()
+    }
+
+    #[test]
+    fn test_object_fields() {
+        let schema = ZodType::object(vec![
+            ZodField::new("name", ZodType::String),
+            ZodField::new("email", ZodType::nullable(ZodType::String)),
+            ZodField::new("age", ZodType::optional(ZodType::Number)),
+        ]);
+        assert!(schema
+            .validate(&json!({ "name": "Ada", "email": null, "extra": 1 }))
+            .is_ok());
+        assert_eq!(
+            issues(&schema, json!({ "email": 1 })),
+            [
+                json!({
+                    "code": "invalid_type",
+                    "expected": "string",
+                    "path": ["name"],
+                    "message": "Invalid input: expected string, received undefined",
+                }),
+                json!({
+                    "code": "invalid_type",
+                    "expected": "string",
+                    "path": ["email"],
+                    "message": "Invalid input: expected string, received number",
+                }),
+            ]
+        );
+        assert_eq!(
+            issues(&schema, json!([])),
+            [json!({
+                "code": "invalid_type",
+                "expected": "object",
+                "path": [],
+                "message": "Invalid input: expected object, received array",
+            })]
+        );
+    }
+
+    #[test]
+    fn test_nested_paths() {
+        let schema = ZodType::record(ZodType::array(ZodType::Boolean));
+        let issues = schema
+            .validate(&json!({ "flags": [true, "no"] }))
+            .unwrap_err();
+        assert_eq!(
+            issues[0].path,
+            [PathSegment::Key("flags".to_string()), PathSegment::Index(1)]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "Invalid input: expected boolean, received string at flags[1]"
+        );
+    }
+
+    #[test]
+    fn test_literal_values() {
+        let status = ZodType::enumeration(vec![
+            ZodEnumVariant::new("Active", "active").with_aliases(&["on"]),
+            ZodEnumVariant::new("Inactive", "inactive"),
+        ]);
+        assert!(status.validate(&json!("on")).is_ok());
+        assert_eq!(
+            issues(&status, json!("off"))[0]["message"],
+            "Invalid option: expected one of \"active\"|\"on\"|\"inactive\""
+        );
+        assert_eq!(
+            issues(&ZodType::literal("A"), json!("B"))[0]["message"],
+            "Invalid input: expected \"A\""
+        );
+
+        let level = ZodType::numeric_enumeration(vec![
+            ZodNumericEnumVariant::new("Low", 1),
+            ZodNumericEnumVariant::new("High", 3),
+        ]);
+        assert!(level.validate(&json!(3)).is_ok());
+        assert_eq!(issues(&level, json!(2))[0]["code"], "invalid_union");
+    }
+
+    #[test]
+    fn test_tuples() {
+        let schema = ZodType::Tuple(vec![ZodType::Number, ZodType::String]);
+        assert!(schema.validate(&json!([1, "a"])).is_ok());
+        assert_eq!(
+            issues(&schema, json!([1, "a", 2]))[0]["message"],
+            "Too big: expected array to have <=2 items"
+        );
+        assert_eq!(issues(&schema, json!([1]))[0]["code"], "too_small");
+
+        // The minimum leaves out trailing optional items
+        let schema = ZodType::Tuple(vec![
+            ZodType::Number,
+            ZodType::String,
+            ZodType::optional(ZodType::Boolean),
+        ]);
+        assert!(schema.validate(&json!([1, "a"])).is_ok());
+        assert_eq!(
+            issues(&schema, json!([1]))[0]["message"],
+            "Too small: expected array to have >=2 items"
+        );
+    }
+
+    #[test]
+    fn test_unions() {
+        let schema = ZodType::Union(vec![ZodType::String, ZodType::Null]);
+        assert!(schema.validate(&json!(null)).is_ok());
+        assert_eq!(
+            issues(&schema, json!(1)),
+            [json!({
+                "code": "invalid_union",
+                "errors": [
+                    [{
+                        "code": "invalid_type",
+                        "expected": "string",
+                        "path": [],
+                        "message": "Invalid input: expected string, received number",
+                    }],
+                    [{
+                        "code": "invalid_type",
+                        "expected": "null",
+                        "path": [],
+                        "message": "Invalid input: expected null, received number",
+                    }],
+                ],
+                "path": [],
+                "message": "Invalid input",
+            })]
+        );
+    }
+
+    #[test]
+    fn test_discriminated_unions() {
+        let variant = |tag: &str, field: ZodField| {
+            ZodType::object(vec![ZodField::new("type", ZodType::literal(tag)), field])
+        };
+        let schema = ZodType::discriminated_union(
+            "type",
+            vec![
+                variant("A", ZodField::new("a", ZodType::Number)),
+                variant("B", ZodField::new("b", ZodType::String)),
+            ],
+        );
+        assert!(schema.validate(&json!({ "type": "B", "b": "x" })).is_ok());
+        // Only the matching member is checked
+        let issues = schema
+            .validate(&json!({ "type": "B", "b": 1 }))
+            .unwrap_err();
+        assert_eq!(issues[0].path, [PathSegment::Key("b".to_string())]);
+        assert_eq!(
+            schema.validate(&json!({ "type": "C" })).unwrap_err()[0].to_json(),
+            json!({
+                "code": "invalid_union",
+                "errors": [],
+                "note": "No matching discriminator",
+                "path": ["type"],
+                "message": "Invalid input",
+            })
+        );
+    }
+
+    #[test]
+    fn test_wrappers() {
+        let defaulted = ZodType::object(vec![ZodField::new(
+            "tags",
+            ZodType::with_default(ZodType::array(ZodType::String), json!([])),
+        )]);
+        assert!(defaulted.validate(&json!({})).is_ok());
+        assert!(defaulted.validate(&json!({ "tags": null })).is_err());
+
+        let other = ZodType::string_except(vec!["a".to_string()]);
+        assert!(other.validate(&json!("b")).is_ok());
+        assert_eq!(issues(&other, json!("a"))[0]["code"], "custom");
+        assert_eq!(issues(&other, json!(5))[0]["code"], "invalid_type");
+
+        let rest = ZodType::catchall(
+            ZodType::object(vec![ZodField::new("name", ZodType::String)]),
+            ZodType::Number,
+        );
+        assert!(rest.validate(&json!({ "name": "x", "n": 1 })).is_ok());
+        assert_eq!(
+            rest.validate(&json!({ "name": "x", "n": "1" }))
+                .unwrap_err()[0]
+                .path,
+            [PathSegment::Key("n".to_string())]
+        );
+
+        let aliased = ZodType::object(vec![
+            ZodField::new("email", ZodType::String).with_aliases(&["mail"])
+        ]);
+        assert!(aliased.validate(&json!({ "mail": "a@b.c" })).is_ok());
+        assert!(aliased.validate(&json!({ "mail": 1 })).is_err());
+
+        // Aliases are renamed before other keys are checked
+        let strict = ZodType::catchall(aliased.clone(), ZodType::Number);
+        assert!(strict.validate(&json!({ "mail": "a@b.c", "n": 1 })).is_ok());
+
+        let tagged = ZodType::discriminated_union(
+            "type",
+            vec![ZodType::object(vec![
+                ZodField::new("type", ZodType::literal("User")),
+                ZodField::new("email", ZodType::String).with_aliases(&["mail"]),
+            ])],
+        );
+        assert!(tagged
+            .validate(&json!({ "type": "User", "mail": "a@b.c" }))
+            .is_ok());
+    }
+}
@@ -0,0 +1,731 @@
+//! Parsing of the `#[serde(...)]` and `#[zod(...)]` attributes the derive
+//! understands.
+//!
+//! Serde attributes that change the serialized shape in ways the derive does
+//! not model are reported as errors rather than ignored, so the generated
+//! schema never silently disagrees with serde.
+
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Attribute, LitStr, Meta, Token};
+
+use crate::Side;
+
+/// Accumulates errors so that every problem is reported in one compile.
+#[derive(Default)]
+pub(crate) struct Errors(Option<syn::Error>);
+
+impl Errors {
+    pub(crate) fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(existing) => existing.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    pub(crate) fn finish(self) -> syn::Result<()> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// How an enum is represented, from its container attributes.
+pub(crate) enum EnumRepresentation {
+    ExternallyTagged,
+    InternallyTagged { tag: LitStr },
+    AdjacentlyTagged { tag: LitStr, content: LitStr },
+    Untagged,
+}
+
+/// A case convention of `rename_all`, applied like serde does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+/// The rules with the names serde accepts for them, in serde's order.
+const RENAME_RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::Lower),
+    ("UPPERCASE", RenameRule::Upper),
+    ("PascalCase", RenameRule::Pascal),
+    ("camelCase", RenameRule::Camel),
+    ("snake_case", RenameRule::Snake),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ("kebab-case", RenameRule::Kebab),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+];
+
+impl RenameRule {
+    /// Parses a rule, with serde's message for unknown ones.
+    fn parse(name: &LitStr) -> syn::Result<Self> {
+        let value = name.value();
+        match RENAME_RULES.iter().find(|(rule, _)| *rule == value) {
+            Some((_, rule)) => Ok(*rule),
+            None => {
+                let expected: Vec<String> = RENAME_RULES
+                    .iter()
+                    .map(|(rule, _)| format!("{rule:?}"))
+                    .collect();
+                Err(syn::Error::new_spanned(
+                    name,
+                    format!(
+                        "unknown rename rule `rename_all = {value:?}`, expected one of {}",
+                        expected.join(", ")
+                    ),
+                ))
+            }
+        }
+    }
+
+    /// Renames a variant, which is assumed to be in PascalCase.
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_string(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => variant[..1].to_ascii_lowercase() + &variant[1..],
+            RenameRule::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => RenameRule::Snake
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::Kebab => RenameRule::Snake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Renames a field, which is assumed to be in snake_case.
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// The `rename_all` rules of each side, from `rename_all = "..."` or
+/// `rename_all(serialize = "...", deserialize = "...")`.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct RenameRules {
+    pub(crate) serialize: Option<RenameRule>,
+    pub(crate) deserialize: Option<RenameRule>,
+}
+
+impl RenameRules {
+    /// These rules, falling back to `other` on the sides they leave alone.
+    pub(crate) fn or(self, other: RenameRules) -> RenameRules {
+        RenameRules {
+            serialize: self.serialize.or(other.serialize),
+            deserialize: self.deserialize.or(other.deserialize),
+        }
+    }
+}
+
+/// Where the value of a missing field comes from when deserializing.
+pub(crate) enum SerdeDefault {
+    /// `default`: `Default::default()`.
+    Default,
+    /// `default = "path"`: calls `path()`.
+    Path(syn::ExprPath),
+}
+
+/// Container-level `#[serde(...)]` options.
+pub(crate) struct SerdeContainer {
+    pub(crate) representation: EnumRepresentation,
+    /// `default`: every field of the struct may be missing when deserializing.
+    pub(crate) default: Option<SerdeDefault>,
+    /// `tag = "..."` on a struct: key of the struct's name, serialized in
+    /// front of its fields.
+    pub(crate) struct_tag: Option<LitStr>,
+    /// Serialized name from `rename = "..."` or `rename(serialize = "...")`.
+    pub(crate) rename: Option<LitStr>,
+    /// `rename_all`: how the fields of a struct or the variants of an enum
+    /// are renamed.
+    pub(crate) rename_all: RenameRules,
+    /// `rename_all_fields`: how the fields of every struct variant are
+    /// renamed, unless the variant has its own `rename_all`.
+    pub(crate) rename_all_fields: RenameRules,
+    /// `transparent`: the struct serializes as its single field.
+    pub(crate) transparent: Option<syn::Path>,
+    /// `from = "..."` or `try_from = "..."`: deserialized through this type.
+    pub(crate) from: Option<syn::Type>,
+    /// `into = "..."`: serialized through this type.
+    pub(crate) into: Option<syn::Type>,
+}
+
+/// Variant- and field-level `#[serde(...)]` options.
+#[derive(Default)]
+pub(crate) struct SerdeMember {
+    /// Serialized name from `rename = "..."` or `rename(serialize = "...")`.
+    pub(crate) rename: Option<LitStr>,
+    /// Deserialized name from `rename = "..."` or `rename(deserialize = "...")`.
+    pub(crate) deserialize_rename: Option<LitStr>,
+    /// Other names accepted when deserializing, from `alias = "..."`.
+    pub(crate) aliases: Vec<LitStr>,
+    /// `skip` or `skip_serializing`.
+    pub(crate) skip_serializing: bool,
+    /// `skip` or `skip_deserializing`.
+    pub(crate) skip_deserializing: bool,
+    /// `skip_serializing_if`: the field may be missing from the output.
+    pub(crate) skip_serializing_if: bool,
+    /// `default`: the field may be missing from the input.
+    pub(crate) default: Option<SerdeDefault>,
+    /// `flatten`: merge the field's object into the surrounding one.
+    pub(crate) flatten: bool,
+    /// `untagged`: the variant is serialized as its bare payload.
+    pub(crate) untagged: bool,
+    /// `other`: the variant deserializes from any unknown tag.
+    pub(crate) other: bool,
+    /// `rename_all` on a variant: how the fields of the variant are renamed.
+    pub(crate) rename_all: RenameRules,
+    /// `deserialize_with` or `with`: a missing field is an error, even for
+    /// an `Option`.
+    pub(crate) deserialize_with: bool,
+    /// The field's type is an `Option`, which serde reads as `None` when the
+    /// field is missing. Set by the caller, which knows the type.
+    pub(crate) option: bool,
+}
+
+impl SerdeMember {
+    /// Names the member by `rules` on the sides it isn't explicitly renamed
+    /// on, like serde does. `ident` is the name of the variant or field.
+    pub(crate) fn rename_by_rules(
+        &mut self,
+        ident: &syn::Ident,
+        rules: RenameRules,
+        is_field: bool,
+    ) {
+        let ident = ident.unraw();
+        let apply = |rule: RenameRule| {
+            let name = if is_field {
+                rule.apply_to_field(&ident.to_string())
+            } else {
+                rule.apply_to_variant(&ident.to_string())
+            };
+            LitStr::new(&name, ident.span())
+        };
+        if self.rename.is_none() {
+            self.rename = rules.serialize.map(apply);
+        }
+        if self.deserialize_rename.is_none() {
+            self.deserialize_rename = rules.deserialize.map(apply);
+        }
+    }
+
+    /// The name on `side`, if renamed.
+    pub(crate) fn name(&self, side: Side) -> Option<&LitStr> {
+        match side {
+            Side::Output => self.rename.as_ref(),
+            Side::Input => self.deserialize_rename.as_ref(),
+        }
+    }
+
+    /// The other names accepted on `side`.
+    pub(crate) fn aliases(&self, side: Side) -> &[LitStr] {
+        match side {
+            Side::Output => &[],
+            Side::Input => &self.aliases,
+        }
+    }
+
+    /// Whether the member is left out on `side`.
+    pub(crate) fn skipped(&self, side: Side) -> bool {
+        match side {
+            Side::Output => self.skip_serializing,
+            Side::Input => self.skip_deserializing,
+        }
+    }
+
+    /// Whether the field may be missing on `side`.
+    pub(crate) fn optional(&self, side: Side) -> bool {
+        match side {
+            Side::Output => self.skip_serializing_if,
+            Side::Input => self.default.is_some() || (self.option && !self.deserialize_with),
+        }
+    }
+}
+
+/// Field-level `#[zod(...)]` options.
+#[derive(Default)]
+pub(crate) struct ZodFieldAttrs {
+    /// `#[zod(as = Type)]`: describe the field with `Type`'s schema.
+    pub(crate) as_type: Option<syn::Type>,
+    /// `#[zod(default = <expr>)]`: the default value, as written in `json!`.
+    pub(crate) default: Option<syn::Expr>,
+    pub(crate) meta: ZodMeta,
+}
+
+/// `#[zod(...)]` options that end up in the schema's `.meta({...})`,
+/// accepted on containers and fields alike.
+#[derive(Default)]
+pub(crate) struct ZodMeta {
+    /// `#[zod(title = "...")]`
+    pub(crate) title: Option<LitStr>,
+    /// `#[zod(example = <expr>)]`, repeatable: any serializable value.
+    pub(crate) examples: Vec<syn::Expr>,
+    /// The note of a Rust `#[deprecated]` attribute: `None` if not
+    /// deprecated, `Some("")` for a bare `#[deprecated]`.
+    pub(crate) deprecated: Option<LitStr>,
+}
+
+impl ZodMeta {
+    /// Parses the option if it is a metadata one, returning whether it was.
+    fn parse(&mut self, meta: &ParseNestedMeta) -> syn::Result<bool> {
+        if meta.path.is_ident("title") {
+            self.title = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("example") {
+            self.examples.push(meta.value()?.parse()?);
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.title.is_none() && self.examples.is_empty() && self.deprecated.is_none()
+    }
+}
+
+/// Container-level `#[zod(...)]` options.
+#[derive(Default)]
+pub(crate) struct ZodContainer {
+    /// `#[zod(export)]`: register the type for `ZodGenerator::from_registry`.
+    pub(crate) export: bool,
+    /// `#[zod(rename = "...")]`: export name used instead of the type name.
+    pub(crate) rename: Option<LitStr>,
+    /// `#[zod(brand)]`: brand the schema with the export name.
+    pub(crate) brand: bool,
+    /// `#[zod(repr)]`: the enum serializes as its discriminant, like with
+    /// `serde_repr`.
+    pub(crate) repr: Option<syn::Path>,
+    pub(crate) meta: ZodMeta,
+}
+
+/// Serde attributes that only affect deserialization or code generation and
+/// therefore leave the serialized shape alone.
+const IGNORED_CONTAINER: &[&str] = &[
+    "bound",
+    "crate",
+    "deny_unknown_fields",
+    "expecting",
+    "field_identifier",
+    "remote",
+    "variant_identifier",
+];
+const IGNORED_VARIANT: &[&str] = &["borrow", "bound"];
+const IGNORED_FIELD: &[&str] = &["borrow", "bound"];
+
+/// Serde attributes that replace a field's (de)serialization, supported
+/// together with `#[zod(as = ...)]`.
+const CUSTOM_SERIALIZATION: &[&str] = &["deserialize_with", "serialize_with", "with"];
+
+/// Serde attributes that change the serialized shape but are not modeled.
+const UNSUPPORTED: &[&str] = &["content", "getter"];
+
+/// Parses the container attributes, pushing problems to `errors` so that
+/// fields and variants can still be checked.
+pub(crate) fn parse_serde_container(
+    attrs: &[Attribute],
+    is_enum: bool,
+    errors: &mut Errors,
+) -> SerdeContainer {
+    let mut tag = None;
+    let mut content = None;
+    let mut untagged = None;
+    let mut default = None;
+    let mut struct_tag = None;
+    let mut rename = None;
+    let mut rename_all = RenameRules::default();
+    let mut rename_all_fields = RenameRules::default();
+    let mut transparent = None;
+    let mut from = None;
+    let mut into = None;
+
+    for_each_serde_meta(attrs, errors, |meta, errors| {
+        if is_enum && meta.path.is_ident("tag") {
+            tag = Some(meta.value()?.parse::<LitStr>()?);
+        } else if is_enum && meta.path.is_ident("content") {
+            content = Some((meta.path.clone(), meta.value()?.parse::<LitStr>()?));
+        } else if is_enum && meta.path.is_ident("untagged") {
+            untagged = Some(meta.path.clone());
+        } else if !is_enum && meta.path.is_ident("tag") {
+            struct_tag = Some(meta.value()?.parse::<LitStr>()?);
+        } else if !is_enum && meta.path.is_ident("default") {
+            default = Some(parse_default(meta)?);
+        } else if meta.path.is_ident("transparent") {
+            transparent = Some(meta.path.clone());
+        } else if meta.path.is_ident("from") || meta.path.is_ident("try_from") {
+            from = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+        } else if meta.path.is_ident("into") {
+            into = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+        } else if meta.path.is_ident("rename") {
+            // Only matters for the tag of a struct
+            rename = parse_rename(meta)?.0.or(rename.take());
+        } else if meta.path.is_ident("rename_all") {
+            rename_all = parse_rename_all(meta)?.or(rename_all);
+        } else if meta.path.is_ident("rename_all_fields") {
+            rename_all_fields = parse_rename_all(meta)?.or(rename_all_fields);
+            if !is_enum {
+                // Same message and span as serde, so that the duplicates are
+                // deduplicated
+                errors.push(meta.error("#[serde(rename_all_fields)] can only be used on enums"));
+            }
+        } else {
+            check_ignored(meta, IGNORED_CONTAINER, errors)?;
+        }
+        Ok(())
+    });
+
+    let representation = match (tag, content, untagged) {
+        // Same messages as serde, so that the duplicates are deduplicated
+        (Some(_), _, Some(untagged)) => {
+            errors.push(syn::Error::new_spanned(
+                untagged,
+                "enum cannot be both untagged and internally tagged",
+            ));
+            EnumRepresentation::Untagged
+        }
+        (None, Some((content, _)), Some(_)) => {
+            errors.push(syn::Error::new_spanned(
+                content,
+                "untagged enum cannot have #[serde(content = \"...\")]",
+            ));
+            EnumRepresentation::Untagged
+        }
+        (None, Some((content, _)), None) => {
+            errors.push(syn::Error::new_spanned(
+                content,
+                "#[serde(tag = \"...\", content = \"...\")] must be used together",
+            ));
+            EnumRepresentation::ExternallyTagged
+        }
+        (None, None, Some(_)) => EnumRepresentation::Untagged,
+        (Some(tag), Some((_, content)), None) => {
+            EnumRepresentation::AdjacentlyTagged { tag, content }
+        }
+        (Some(tag), None, None) => EnumRepresentation::InternallyTagged { tag },
+        (None, None, None) => EnumRepresentation::ExternallyTagged,
+    };
+
+    SerdeContainer {
+        representation,
+        default,
+        struct_tag,
+        rename,
+        rename_all,
+        rename_all_fields,
+        transparent,
+        from,
+        into,
+    }
+}
+
+pub(crate) fn parse_serde_variant(attrs: &[Attribute]) -> syn::Result<SerdeMember> {
+    parse_serde_member(attrs, IGNORED_VARIANT, false)
+}
+
+/// Parses field attributes. `overridden` fields have a `#[zod(as = ...)]`
+/// schema, which makes custom serialization functions fine.
+pub(crate) fn parse_serde_field(attrs: &[Attribute], overridden: bool) -> syn::Result<SerdeMember> {
+    if overridden {
+        let ignored: Vec<&str> = IGNORED_FIELD
+            .iter()
+            .chain(CUSTOM_SERIALIZATION)
+            .copied()
+            .collect();
+        parse_serde_member(attrs, &ignored, true)
+    } else {
+        parse_serde_member(attrs, IGNORED_FIELD, true)
+    }
+}
+
+fn parse_serde_member(
+    attrs: &[Attribute],
+    ignored: &[&str],
+    is_field: bool,
+) -> syn::Result<SerdeMember> {
+    let mut errors = Errors::default();
+    let mut parsed = SerdeMember::default();
+
+    for_each_serde_meta(attrs, &mut errors, |meta, errors| {
+        if meta.path.is_ident("rename") {
+            let (serialize, deserialize) = parse_rename(meta)?;
+            parsed.rename = serialize.or(parsed.rename.take());
+            parsed.deserialize_rename = deserialize.or(parsed.deserialize_rename.take());
+        } else if meta.path.is_ident("alias") {
+            parsed.aliases.push(meta.value()?.parse()?);
+        } else if meta.path.is_ident("skip") {
+            parsed.skip_serializing = true;
+            parsed.skip_deserializing = true;
+        } else if meta.path.is_ident("skip_serializing") {
+            parsed.skip_serializing = true;
+        } else if meta.path.is_ident("skip_deserializing") {
+            parsed.skip_deserializing = true;
+        } else if is_field && meta.path.is_ident("skip_serializing_if") {
+            parsed.skip_serializing_if = true;
+            skip_value(meta)?;
+        } else if is_field && meta.path.is_ident("default") {
+            parsed.default = Some(parse_default(meta)?);
+        } else if is_field && meta.path.is_ident("flatten") {
+            parsed.flatten = true;
+        } else if !is_field && meta.path.is_ident("untagged") {
+            parsed.untagged = true;
+        } else if !is_field && meta.path.is_ident("other") {
+            parsed.other = true;
+        } else if !is_field && meta.path.is_ident("rename_all") {
+            parsed.rename_all = parse_rename_all(meta)?.or(parsed.rename_all);
+        } else if !is_field
+            && CUSTOM_SERIALIZATION
+                .iter()
+                .any(|name| meta.path.is_ident(name))
+        {
+            // Variants have no #[zod(as = ...)] to describe the custom form
+            let name = meta.path.get_ident().unwrap();
+            errors.push(syn::Error::new_spanned(
+                &meta.path,
+                format!("#[serde({name})] is not supported on variants by the ZodSchema derive"),
+            ));
+            skip_value(meta)?;
+        } else {
+            if is_field && (meta.path.is_ident("with") || meta.path.is_ident("deserialize_with")) {
+                parsed.deserialize_with = true;
+            }
+            check_ignored(meta, ignored, errors)?;
+        }
+        Ok(())
+    });
+
+    errors.finish()?;
+    Ok(parsed)
+}
+
+pub(crate) fn parse_zod_field(attrs: &[Attribute]) -> syn::Result<ZodFieldAttrs> {
+    let mut parsed = ZodFieldAttrs::default();
+    parsed.meta.deprecated = deprecation(attrs);
+    for attr in attrs {
+        if !attr.path().is_ident("zod") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("as") {
+                parsed.as_type = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                parsed.default = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if parsed.meta.parse(&meta)? {
+                Ok(())
+            } else {
+                Err(meta.error("unknown #[zod(...)] field attribute"))
+            }
+        })?;
+    }
+    Ok(parsed)
+}
+
+pub(crate) fn parse_zod_container(attrs: &[Attribute]) -> syn::Result<ZodContainer> {
+    let mut parsed = ZodContainer::default();
+    parsed.meta.deprecated = deprecation(attrs);
+    for attr in attrs {
+        if !attr.path().is_ident("zod") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("export") {
+                parsed.export = true;
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                parsed.rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("brand") {
+                parsed.brand = true;
+                Ok(())
+            } else if meta.path.is_ident("repr") {
+                parsed.repr = Some(meta.path.clone());
+                Ok(())
+            } else if parsed.meta.parse(&meta)? {
+                Ok(())
+            } else {
+                Err(meta.error("unknown #[zod(...)] container attribute"))
+            }
+        })?;
+    }
+    Ok(parsed)
+}
+
+/// The note of a `#[deprecated]`, `#[deprecated = "..."]` or
+/// `#[deprecated(note = "...")]` attribute: `None` if not deprecated,
+/// `Some("")` for a bare `#[deprecated]`. Malformed attributes are left to
+/// the compiler to report.
+pub(crate) fn deprecation(attrs: &[Attribute]) -> Option<LitStr> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("deprecated"))?;
+    let mut note = LitStr::new("", attr.span());
+    match &attr.meta {
+        Meta::Path(_) => {}
+        Meta::NameValue(meta) => {
+            if let Ok(value) = syn::parse2(meta.value.to_token_stream()) {
+                note = value;
+            }
+        }
+        Meta::List(_) => {
+            let _ = attr.parse_nested_meta(|meta| {
+                let value: LitStr = meta.value()?.parse()?;
+                if meta.path.is_ident("note") {
+                    note = value;
+                }
+                Ok(())
+            });
+        }
+    }
+    Some(note)
+}
+
+/// Calls `f` for every option of every `#[serde(...)]` attribute, collecting
+/// errors instead of stopping at the first one.
+fn for_each_serde_meta(
+    attrs: &[Attribute],
+    errors: &mut Errors,
+    mut f: impl FnMut(&ParseNestedMeta, &mut Errors) -> syn::Result<()>,
+) {
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        let mut nested = Errors::default();
+        let result = attr.parse_nested_meta(|meta| f(&meta, &mut nested));
+        if let Err(error) = result {
+            nested.push(error);
+        }
+        if let Err(error) = nested.finish() {
+            errors.push(error);
+        }
+    }
+}
+
+/// Parses `rename = "..."` or `rename(serialize = "...", deserialize = "...")`,
+/// returning the serialized and the deserialized name.
+fn parse_rename(meta: &ParseNestedMeta) -> syn::Result<(Option<LitStr>, Option<LitStr>)> {
+    if meta.input.peek(Token![=]) {
+        let name: LitStr = meta.value()?.parse()?;
+        return Ok((Some(name.clone()), Some(name)));
+    }
+    let mut serialize = None;
+    let mut deserialize = None;
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("serialize") {
+            serialize = Some(nested.value()?.parse()?);
+            Ok(())
+        } else if nested.path.is_ident("deserialize") {
+            deserialize = Some(nested.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(nested.error("expected `serialize` or `deserialize`"))
+        }
+    })?;
+    Ok((serialize, deserialize))
+}
+
+/// Parses `rename_all = "..."` or
+/// `rename_all(serialize = "...", deserialize = "...")`.
+fn parse_rename_all(meta: &ParseNestedMeta) -> syn::Result<RenameRules> {
+    let (serialize, deserialize) = parse_rename(meta)?;
+    let parse = |name: Option<LitStr>| name.as_ref().map(RenameRule::parse).transpose();
+    Ok(RenameRules {
+        serialize: parse(serialize)?,
+        deserialize: parse(deserialize)?,
+    })
+}
+
+/// Parses `default` or `default = "path"`.
+fn parse_default(meta: &ParseNestedMeta) -> syn::Result<SerdeDefault> {
+    if meta.input.peek(Token![=]) {
+        let path: LitStr = meta.value()?.parse()?;
+        Ok(SerdeDefault::Path(path.parse()?))
+    } else {
+        Ok(SerdeDefault::Default)
+    }
+}
+
+/// Accepts options in `ignored`, reports everything else.
+fn check_ignored(meta: &ParseNestedMeta, ignored: &[&str], errors: &mut Errors) -> syn::Result<()> {
+    let name = meta
+        .path
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    if !ignored.contains(&name.as_str()) {
+        let message = if CUSTOM_SERIALIZATION.contains(&name.as_str()) {
+            let form = if name == "deserialize_with" {
+                "deserialized"
+            } else {
+                "serialized"
+            };
+            format!(
+                "#[serde({name})] changes the {form} form; \
+                 describe it with #[zod(as = Type)]"
+            )
+        } else if UNSUPPORTED.contains(&name.as_str()) {
+            format!("#[serde({name})] is not supported by the ZodSchema derive")
+        } else {
+            format!("unknown serde attribute `{name}`")
+        };
+        errors.push(syn::Error::new_spanned(&meta.path, message));
+    }
+    skip_value(meta)
+}
+
+/// Consumes the value of an option, if any: `= <expr>` or `(...)`.
+fn skip_value(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_value(&nested))?;
+    }
+    Ok(())
+}
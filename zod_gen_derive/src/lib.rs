@@ -1,457 +1,953 @@
 extern crate proc_macro;
 
+mod attr;
+
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
-
-#[derive(Clone)]
-enum EnumRepresentation {
-    ExternallyTagged,
-    InternallyTagged { tag: String },
-    AdjacentlyTagged { tag: String, content: String },
-    Untagged,
-}
-
-fn find_serde_rename_from_attrs(attrs: &[Attribute]) -> Option<String> {
-    for attr in attrs {
-        if attr.path().is_ident("serde") {
-            let attr_str = quote!(#attr).to_string();
-            if let Some(rename_start) = attr_str.find("rename") {
-                let rename_part = &attr_str[rename_start..];
-                if let Some(quote_start) = rename_part.find('"') {
-                    if let Some(quote_end) = rename_part[quote_start + 1..].find('"') {
-                        let rename_value =
-                            &rename_part[quote_start + 1..quote_start + 1 + quote_end];
-                        return Some(rename_value.to_string());
-                    }
-                }
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+use attr::{EnumRepresentation, Errors};
+
+/// Which schema is being generated: the JSON a value serializes to, or the
+/// JSON accepted when deserializing.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    Output,
+    Input,
+}
+
+/// Parses a field's attributes, returning its serde and zod options and the
+/// type describing it: its own type or the `#[zod(as = ...)]` override.
+fn field_type(
+    field: &syn::Field,
+) -> syn::Result<(attr::SerdeMember, attr::ZodFieldAttrs, syn::Type)> {
+    let zod = attr::parse_zod_field(&field.attrs);
+    let serde = attr::parse_serde_field(
+        &field.attrs,
+        zod.as_ref().is_ok_and(|zod| zod.as_type.is_some()),
+    );
+    let (zod, serde) = match (zod, serde) {
+        (Ok(zod), Ok(serde)) => (zod, serde),
+        (Err(mut error), Err(other)) => {
+            error.combine(other);
+            return Err(error);
+        }
+        (Err(error), _) | (_, Err(error)) => return Err(error),
+    };
+    let mut serde = serde;
+    serde.option = is_named(&field.ty, "Option");
+    let ty = zod.as_type.clone().unwrap_or_else(|| field.ty.clone());
+    Ok((serde, zod, ty))
+}
+
+/// Builds the schema of a field's type.
+///
+/// The schema is spanned on the type, so that a missing `ZodSchema` impl is
+/// reported at the field rather than in generated code.
+fn type_schema(ty: &syn::Type, side: Side) -> proc_macro2::TokenStream {
+    let input = side == Side::Input;
+    quote_spanned! {ty.span()=> zod_gen::__private::field_schema::<#ty>(#input) }
+}
+
+/// The fields of an object built from named fields, with `#[serde(flatten)]`
+/// fields merged in.
+struct ObjectFields {
+    /// A `Vec<zod_gen::ZodField>` expression.
+    fields: proc_macro2::TokenStream,
+    /// An `Option<zod_gen::ZodType>` expression for other keys, if any field
+    /// is flattened.
+    catchall: Option<proc_macro2::TokenStream>,
+}
+
+impl ObjectFields {
+    /// The `zod_gen::ZodType` expression of the object.
+    fn object_type(&self) -> proc_macro2::TokenStream {
+        let fields = &self.fields;
+        match &self.catchall {
+            None => quote! { zod_gen::ZodType::object(#fields) },
+            Some(catchall) => quote! { zod_gen::__private::object(#fields, #catchall) },
+        }
+    }
+}
+
+/// Wraps `schema` with the `#[zod(title, example)]` metadata and the
+/// `deprecated` flag, if any.
+fn with_meta(schema: proc_macro2::TokenStream, meta: &attr::ZodMeta) -> proc_macro2::TokenStream {
+    if meta.is_empty() {
+        return schema;
+    }
+    let mut entries = Vec::new();
+    if let Some(title) = &meta.title {
+        entries.push(quote! { ("title", zod_gen::__private::serde_json::Value::from(#title)) });
+    }
+    if meta.deprecated.is_some() {
+        entries.push(quote! { ("deprecated", zod_gen::__private::serde_json::Value::Bool(true)) });
+    }
+    if !meta.examples.is_empty() {
+        let examples = meta.examples.iter().map(|example| {
+            quote! {
+                zod_gen::__private::serde_json::to_value(&#example)
+                    .expect("#[zod(example = ...)] must serialize to JSON")
             }
+        });
+        entries.push(quote! {
+            ("examples", zod_gen::__private::serde_json::Value::Array(vec![#(#examples),*]))
+        });
+    }
+    quote! { zod_gen::__private::meta(#schema, vec![#(#entries),*]) }
+}
+
+/// Builds the fields of an object on `side`, after the `leading`
+/// `zod_gen::ZodField` expressions. Fields are named by `rename_all` unless
+/// renamed themselves. With a `container_default`, every field may be
+/// missing from the input.
+fn object_fields(
+    fields: &syn::FieldsNamed,
+    leading: Vec<proc_macro2::TokenStream>,
+    side: Side,
+    rename_all: attr::RenameRules,
+    container_default: Option<&attr::SerdeDefault>,
+) -> syn::Result<ObjectFields> {
+    enum Part {
+        Field(proc_macro2::TokenStream),
+        Flatten(syn::Type),
+    }
+
+    let parts = collect(fields.named.iter().map(|f| {
+        let ident = f.ident.as_ref().unwrap();
+        let (mut serde, zod, ty) = field_type(f)?;
+        serde.rename_by_rules(ident, rename_all, true);
+        if let (Some(default), None, None) = (&zod.default, &serde.default, container_default) {
+            return Err(syn::Error::new_spanned(
+                default,
+                "#[zod(default = ...)] requires #[serde(default)] on the field or the struct",
+            ));
+        }
+        if serde.skipped(side) {
+            return Ok(None);
+        }
+        if serde.flatten {
+            return Ok(Some(Part::Flatten(ty)));
         }
+        let name_lit = serde
+            .name(side)
+            .cloned()
+            .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+        let mut schema = with_meta(type_schema(&ty, side), &zod.meta);
+        if side == Side::Input && (serde.default.is_some() || container_default.is_some()) {
+            let value = default_value(f, &serde, &zod, container_default);
+            schema = quote! { zod_gen::__private::with_default(#schema, #value) };
+        } else if serde.optional(side) {
+            schema = quote! { zod_gen::ZodType::optional(#schema) };
+        }
+        Ok(Some(Part::Field(with_deprecation(
+            with_aliases(
+                quote! { zod_gen::ZodField::new(#name_lit, #schema) },
+                serde.aliases(side),
+            ),
+            &zod.meta.deprecated,
+        ))))
+    }))?;
+    let parts: Vec<Part> = parts.into_iter().flatten().collect();
+
+    let flattened: Vec<&syn::Type> = parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Flatten(ty) => Some(ty),
+            Part::Field(_) => None,
+        })
+        .collect();
+    if flattened.is_empty() {
+        let fields = parts.iter().map(|part| match part {
+            Part::Field(field) => field,
+            Part::Flatten(_) => unreachable!(),
+        });
+        return Ok(ObjectFields {
+            fields: quote! { vec![#(#leading,)* #(#fields),*] },
+            catchall: None,
+        });
+    }
+
+    let input = side == Side::Input;
+    let catchall = match side {
+        Side::Output => quote! { catchall },
+        Side::Input => quote! { input_catchall },
+    };
+    let steps = parts.iter().map(|part| match part {
+        Part::Field(field) => quote! { fields.push(#field); },
+        // Spanned on the flattened type, which must be an object
+        Part::Flatten(ty) => quote_spanned! {ty.span()=>
+            fields.extend(zod_gen::__private::flattened_fields::<#ty>(#input));
+        },
+    });
+    Ok(ObjectFields {
+        fields: quote! {{
+            let mut fields: Vec<zod_gen::ZodField> = vec![#(#leading),*];
+            #(#steps)*
+            fields
+        }},
+        catchall: Some(quote! {
+            [#(<#flattened as zod_gen::ZodObjectSchema>::#catchall()),*]
+                .into_iter()
+                .flatten()
+                .next()
+        }),
+    })
+}
+
+/// Builds the `Option<serde_json::Value>` expression of the value serde fills
+/// in for a missing field: `#[zod(default = ...)]`, or the serialized
+/// default if the type implements `Serialize`.
+fn default_value(
+    field: &syn::Field,
+    serde: &attr::SerdeMember,
+    zod: &attr::ZodFieldAttrs,
+    container_default: Option<&attr::SerdeDefault>,
+) -> proc_macro2::TokenStream {
+    if let Some(value) = &zod.default {
+        return quote! { Some(zod_gen::__private::serde_json::json!(#value)) };
+    }
+    // The field's own type says nothing about the serialized form
+    if zod.as_type.is_some() {
+        return quote! { None };
     }
-    None
+    let serialized = |function: proc_macro2::TokenStream| {
+        quote! {{
+            use zod_gen::__private::{NoDefault as _, SerializeDefault as _};
+            (&zod_gen::__private::DefaultValue(#function)).default_json()
+        }}
+    };
+    let ty = &field.ty;
+    match (&serde.default, container_default) {
+        (Some(attr::SerdeDefault::Default), _) => {
+            serialized(quote! { <#ty as ::core::default::Default>::default })
+        }
+        (Some(attr::SerdeDefault::Path(path)), _) => serialized(quote! { #path }),
+        // The field's value in the container's default
+        (None, Some(default)) => {
+            let container = match default {
+                attr::SerdeDefault::Default => {
+                    serialized(quote! { <Self as ::core::default::Default>::default })
+                }
+                attr::SerdeDefault::Path(path) => serialized(quote! { #path }),
+            };
+            let ident = field.ident.as_ref().unwrap();
+            let name = serde
+                .name(Side::Output)
+                .cloned()
+                .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+            quote! { #container.and_then(|value| value.get(#name).cloned()) }
+        }
+        (None, None) => quote! { None },
+    }
+}
+
+/// Builds the schema of a newtype variant's single field.
+fn newtype_schema(
+    fields: &syn::FieldsUnnamed,
+    side: Side,
+) -> syn::Result<proc_macro2::TokenStream> {
+    Ok(type_schema(
+        &field_type(fields.unnamed.first().unwrap())?.2,
+        side,
+    ))
+}
+
+/// Builds a `zod_gen::ZodType::Tuple` for a set of unnamed fields.
+fn tuple_type(fields: &syn::FieldsUnnamed, side: Side) -> syn::Result<proc_macro2::TokenStream> {
+    let items = collect(
+        fields
+            .unnamed
+            .iter()
+            .map(|f| Ok(type_schema(&field_type(f)?.2, side))),
+    )?;
+    Ok(quote! { zod_gen::ZodType::Tuple(vec![#(#items),*]) })
+}
+
+/// Builds the schema of a variant's payload; `None` for unit variants.
+fn variant_payload(
+    variant: &syn::Variant,
+    serde: &attr::SerdeMember,
+    side: Side,
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    Ok(match &variant.fields {
+        Fields::Unit => None,
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Some(newtype_schema(fields, side)?),
+        Fields::Unnamed(fields) => Some(tuple_type(fields, side)?),
+        Fields::Named(fields) => {
+            Some(object_fields(fields, Vec::new(), side, serde.rename_all, None)?.object_type())
+        }
+    })
 }
 
-fn extract_serde_rename_variant(variant: &syn::Variant) -> String {
-    if let Some(rename_value) = find_serde_rename_from_attrs(&variant.attrs) {
-        rename_value
+/// The name of a variant on `side`.
+fn variant_name(variant: &syn::Variant, serde: &attr::SerdeMember, side: Side) -> LitStr {
+    serde
+        .name(side)
+        .cloned()
+        .unwrap_or_else(|| LitStr::new(&variant.ident.to_string(), variant.ident.span()))
+}
+
+/// Appends `.with_aliases(...)` to a `zod_gen::ZodField` or
+/// `zod_gen::ZodEnumVariant` expression, if there are any.
+fn with_aliases(expr: proc_macro2::TokenStream, aliases: &[LitStr]) -> proc_macro2::TokenStream {
+    if aliases.is_empty() {
+        expr
     } else {
-        variant.ident.to_string()
+        quote! { #expr.with_aliases(&[#(#aliases),*]) }
     }
 }
 
-fn find_serde_tag_from_attrs(attrs: &[Attribute]) -> Option<String> {
-    for attr in attrs {
-        if attr.path().is_ident("serde") {
-            let attr_str = quote!(#attr).to_string();
-            if let Some(tag_start) = attr_str.find("tag") {
-                let tag_part = &attr_str[tag_start..];
-                if let Some(quote_start) = tag_part.find('"') {
-                    if let Some(quote_end) = tag_part[quote_start + 1..].find('"') {
-                        let tag_value = &tag_part[quote_start + 1..quote_start + 1 + quote_end];
-                        return Some(tag_value.to_string());
-                    }
-                }
-            }
+/// Appends `.with_deprecation(...)` to a `zod_gen::ZodField`, variant or
+/// named `zod_gen::ZodType` expression for a `#[deprecated]` item.
+fn with_deprecation(
+    expr: proc_macro2::TokenStream,
+    note: &Option<LitStr>,
+) -> proc_macro2::TokenStream {
+    match note {
+        Some(note) => quote! { #expr.with_deprecation(#note) },
+        None => expr,
+    }
+}
+
+/// Marks the schema of a `#[deprecated]` variant as deprecated in its
+/// metadata.
+fn deprecated_variant(
+    schema: proc_macro2::TokenStream,
+    variant: &syn::Variant,
+) -> proc_macro2::TokenStream {
+    let meta = attr::ZodMeta {
+        deprecated: attr::deprecation(&variant.attrs),
+        ..Default::default()
+    };
+    with_meta(schema, &meta)
+}
+
+/// Builds the schema of a unit variant's tag: `z.literal("<variant>")`, or a
+/// `z.enum` of the name and its aliases.
+fn tag_schema(
+    variant: &syn::Variant,
+    serde: &attr::SerdeMember,
+    side: Side,
+) -> proc_macro2::TokenStream {
+    if serde.aliases(side).is_empty() {
+        let var_lit = variant_name(variant, serde, side);
+        return quote! { zod_gen::ZodType::literal(#var_lit) };
+    }
+    let enum_variant = enum_variant(variant, serde, side);
+    quote! { zod_gen::ZodType::enumeration(vec![#enum_variant]) }
+}
+
+/// Builds the `zod_gen::ZodEnumVariant` of a unit variant.
+fn enum_variant(
+    variant: &syn::Variant,
+    serde: &attr::SerdeMember,
+    side: Side,
+) -> proc_macro2::TokenStream {
+    let var_lit = variant_name(variant, serde, side);
+    let ident = LitStr::new(&variant.ident.unraw().to_string(), variant.ident.span());
+    with_deprecation(
+        with_aliases(
+            quote! { zod_gen::ZodEnumVariant::new(#ident, #var_lit) },
+            serde.aliases(side),
+        ),
+        &attr::deprecation(&variant.attrs),
+    )
+}
+
+/// Builds the `{ "<tag>": z.literal("<variant>") }` field used by tagged enums.
+fn tag_field(
+    tag_lit: &LitStr,
+    variant: &syn::Variant,
+    serde: &attr::SerdeMember,
+    side: Side,
+) -> proc_macro2::TokenStream {
+    let schema = tag_schema(variant, serde, side);
+    quote! { zod_gen::ZodField::new(#tag_lit, #schema) }
+}
+
+/// Collects the successful items, or every error at once.
+fn collect<T>(items: impl Iterator<Item = syn::Result<T>>) -> syn::Result<Vec<T>> {
+    let mut errors = Errors::default();
+    let mut collected = Vec::new();
+    for item in items {
+        match item {
+            Ok(item) => collected.push(item),
+            Err(error) => errors.push(error),
         }
     }
-    None
-}
-
-fn find_serde_content_from_attrs(attrs: &[Attribute]) -> Option<String> {
-    for attr in attrs {
-        if attr.path().is_ident("serde") {
-            let attr_str = quote!(#attr).to_string();
-            if let Some(content_start) = attr_str.find("content") {
-                let content_part = &attr_str[content_start..];
-                if let Some(quote_start) = content_part.find('"') {
-                    if let Some(quote_end) = content_part[quote_start + 1..].find('"') {
-                        let content_value =
-                            &content_part[quote_start + 1..quote_start + 1 + quote_end];
-                        return Some(content_value.to_string());
-                    }
-                }
+    errors.finish()?;
+    Ok(collected)
+}
+
+/// Schema of a `#[zod(repr)]` enum: the discriminants of its unit variants,
+/// computed by the compiler.
+fn numeric_enum_body(data_enum: &syn::DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let enum_variants = collect(data_enum.variants.iter().map(|v| {
+        if !matches!(v.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                &v.fields,
+                "#[zod(repr)] requires unit variants",
+            ));
+        }
+        let ident = &v.ident;
+        let name = LitStr::new(&ident.unraw().to_string(), ident.span());
+        Ok(with_deprecation(
+            quote! { zod_gen::ZodNumericEnumVariant::new(#name, Self::#ident as i64) },
+            &attr::deprecation(&v.attrs),
+        ))
+    }))?;
+
+    Ok(quote! { zod_gen::ZodType::numeric_enumeration(vec![#(#enum_variants),*]) })
+}
+
+fn enum_body(
+    data_enum: &syn::DataEnum,
+    serde_attrs: &attr::SerdeContainer,
+    side: Side,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let representation = &serde_attrs.representation;
+    let variants = collect(data_enum.variants.iter().map(|v| {
+        let mut serde = attr::parse_serde_variant(&v.attrs)?;
+        serde.rename_by_rules(&v.ident, serde_attrs.rename_all, false);
+        // A variant's own `rename_all` takes precedence for its fields
+        serde.rename_all = serde.rename_all.or(serde_attrs.rename_all_fields);
+        Ok((v, serde))
+    }))?;
+    if !matches!(representation, EnumRepresentation::Untagged) {
+        check_variant_order(&variants)?;
+    }
+    let has_other = other_variant(&variants, representation, side)?;
+    let variants: Vec<_> = variants
+        .into_iter()
+        .filter(|(_, serde)| !serde.skipped(side))
+        .collect();
+
+    // Untagged variants come last; an untagged enum has only untagged variants
+    let first_untagged = match representation {
+        EnumRepresentation::Untagged => 0,
+        _ => variants
+            .iter()
+            .position(|(_, serde)| serde.untagged)
+            .unwrap_or(variants.len()),
+    };
+    let (tagged, untagged) = variants.split_at(first_untagged);
+
+    let untagged_schemas = collect(untagged.iter().map(|(v, serde)| {
+        let schema =
+            variant_payload(v, serde, side)?.unwrap_or_else(|| quote! { zod_gen::ZodType::Null });
+        Ok(deprecated_variant(schema, v))
+    }))?;
+    let tagged_schema = match representation {
+        EnumRepresentation::Untagged => None,
+        _ if tagged.is_empty() && !untagged.is_empty() => None,
+        _ => Some(tagged_enum_body(tagged, representation, side)?),
+    };
+
+    let fallback = has_other.then(|| other_fallback(tagged, representation, side));
+
+    Ok(match tagged_schema {
+        Some(TaggedSchema::Schema(schema)) if untagged_schemas.is_empty() && fallback.is_none() => {
+            schema
+        }
+        // Serde tries the tagged variants first, then each untagged one
+        tagged_schema => {
+            let schemas = match tagged_schema {
+                None => Vec::new(),
+                Some(TaggedSchema::Schema(schema)) => vec![schema],
+                Some(TaggedSchema::Members(members)) => members,
             }
+            .into_iter()
+            .chain(fallback)
+            .chain(untagged_schemas);
+            quote! { zod_gen::ZodType::Union(vec![#(#schemas),*]) }
         }
+    })
+}
+
+/// The union member of unknown tags, which serde reads as the
+/// `#[serde(other)]` variant: its tag is any string but the known ones, so
+/// the known variants' payloads are still checked.
+fn other_fallback(
+    tagged: &[(&syn::Variant, attr::SerdeMember)],
+    representation: &EnumRepresentation,
+    side: Side,
+) -> proc_macro2::TokenStream {
+    let known = tagged.iter().flat_map(|(v, serde)| {
+        std::iter::once(variant_name(v, serde, side)).chain(serde.aliases(side).iter().cloned())
+    });
+    let other = quote! {
+        zod_gen::ZodType::string_except(vec![#(#known.to_string()),*])
+    };
+    match representation {
+        EnumRepresentation::InternallyTagged { tag }
+        | EnumRepresentation::AdjacentlyTagged { tag, .. } => quote! {
+            zod_gen::ZodType::object(vec![zod_gen::ZodField::new(#tag, #other)])
+        },
+        _ => other,
     }
-    None
 }
 
-fn has_serde_untagged(attrs: &[Attribute]) -> bool {
-    for attr in attrs {
-        if attr.path().is_ident("serde") {
-            let attr_str = quote!(#attr).to_string();
-            if attr_str.contains("untagged") {
-                return true;
+/// Checks that untagged variants are not followed by tagged ones, as serde
+/// requires, with serde's message.
+fn check_variant_order(variants: &[(&syn::Variant, attr::SerdeMember)]) -> syn::Result<()> {
+    let mut errors = Errors::default();
+    if let Some(last_tagged) = variants.iter().rposition(|(_, serde)| !serde.untagged) {
+        for (v, serde) in &variants[..last_tagged] {
+            if serde.untagged {
+                errors.push(syn::Error::new_spanned(
+                    &v.ident,
+                    "all variants with the #[serde(untagged)] attribute must be placed at the end of the enum",
+                ));
             }
         }
     }
-    false
+    errors.finish()
 }
 
-fn parse_enum_serde_attrs(attrs: &[Attribute]) -> Result<EnumRepresentation, String> {
-    let has_untagged = has_serde_untagged(attrs);
-    let tag = find_serde_tag_from_attrs(attrs);
-    let content = find_serde_content_from_attrs(attrs);
-
-    if has_untagged {
-        if tag.is_some() || content.is_some() {
-            return Err("enum cannot be both untagged and internally tagged".to_string());
+/// Whether unknown tags fall back to a `#[serde(other)]` variant, checked
+/// like serde does. Serde only falls back to it when deserializing, so never
+/// on the output side.
+fn other_variant(
+    variants: &[(&syn::Variant, attr::SerdeMember)],
+    representation: &EnumRepresentation,
+    side: Side,
+) -> syn::Result<bool> {
+    let mut errors = Errors::default();
+    let mut other = false;
+    for (i, (v, serde)) in variants.iter().enumerate() {
+        if !serde.other {
+            continue;
         }
-        return Ok(EnumRepresentation::Untagged);
+        let message = if matches!(representation, EnumRepresentation::Untagged) {
+            "#[serde(other)] cannot appear on untagged enum"
+        } else if !matches!(v.fields, Fields::Unit) {
+            "#[serde(other)] must be on a unit variant"
+        } else if i < variants.len() - 1 {
+            "#[serde(other)] must be on the last variant"
+        } else {
+            // An untagged fallback is serialized without its name
+            other = side == Side::Input && !serde.untagged;
+            continue;
+        };
+        errors.push(syn::Error::new_spanned(v, message));
     }
+    errors.finish()?;
+    Ok(other)
+}
 
-    if let Some(t) = tag {
-        if let Some(c) = content {
-            return Ok(EnumRepresentation::AdjacentlyTagged { tag: t, content: c });
-        } else {
-            return Ok(EnumRepresentation::InternallyTagged { tag: t });
+/// The schema of the variants serialized with their tag.
+enum TaggedSchema {
+    Schema(proc_macro2::TokenStream),
+    /// Members of a `z.union`, which untagged variants join.
+    Members(Vec<proc_macro2::TokenStream>),
+}
+
+/// Builds the schema of the variants serialized with their tag.
+fn tagged_enum_body(
+    variants: &[(&syn::Variant, attr::SerdeMember)],
+    representation: &EnumRepresentation,
+    side: Side,
+) -> syn::Result<TaggedSchema> {
+    let iter = variants.iter();
+    Ok(match representation {
+        EnumRepresentation::ExternallyTagged
+            if !variants.is_empty()
+                && variants
+                    .iter()
+                    .all(|(v, _)| matches!(v.fields, Fields::Unit)) =>
+        {
+            // Only unit variants: a `z.enum` of their serialized names
+            let enum_variants = iter.map(|(v, serde)| enum_variant(v, serde, side));
+
+            TaggedSchema::Schema(
+                quote! { zod_gen::ZodType::enumeration(vec![#(#enum_variants),*]) },
+            )
         }
-    }
+        EnumRepresentation::ExternallyTagged => {
+            TaggedSchema::Members(collect(iter.map(|(v, serde)| {
+                let var_lit = variant_name(v, serde, side);
+                let schema = match variant_payload(v, serde, side)? {
+                    None => tag_schema(v, serde, side),
+                    Some(payload) => {
+                        let field = with_aliases(
+                            quote! { zod_gen::ZodField::new(#var_lit, #payload) },
+                            serde.aliases(side),
+                        );
+                        quote! { zod_gen::ZodType::object(vec![#field]) }
+                    }
+                };
+                Ok(deprecated_variant(schema, v))
+            }))?)
+        }
+        EnumRepresentation::InternallyTagged { tag } => {
+            let variant_schemas = collect(iter.map(|(v, serde)| {
+                let tag_field = tag_field(tag, v, serde, side);
+
+                let schema = match &v.fields {
+                    Fields::Unit => {
+                        quote! { zod_gen::ZodType::object(vec![#tag_field]) }
+                    }
+                    Fields::Unnamed(fields) if fields.unnamed.len() != 1 => {
+                        return Err(syn::Error::new_spanned(
+                            v,
+                            "#[serde(tag = \"...\")] cannot be used with tuple variants",
+                        ));
+                    }
+                    Fields::Unnamed(fields) => {
+                        // Spanned on the payload type, which must be an object
+                        let (_, _, ty) = field_type(fields.unnamed.first().unwrap())?;
+                        let input = side == Side::Input;
+                        quote_spanned! {ty.span()=>
+                            zod_gen::__private::internally_tagged::<#ty>(#tag_field, #input)
+                        }
+                    }
+                    Fields::Named(fields) => {
+                        object_fields(fields, vec![tag_field], side, serde.rename_all, None)?
+                            .object_type()
+                    }
+                };
+                Ok(deprecated_variant(schema, v))
+            }))?;
 
-    Ok(EnumRepresentation::ExternallyTagged)
+            TaggedSchema::Schema(
+                quote! { zod_gen::ZodType::discriminated_union(#tag, vec![#(#variant_schemas),*]) },
+            )
+        }
+        EnumRepresentation::AdjacentlyTagged { tag, content } => {
+            let variant_schemas = collect(iter.map(|(v, serde)| {
+                let tag_field = tag_field(tag, v, serde, side);
+
+                let schema = match variant_payload(v, serde, side)? {
+                    None => quote! { zod_gen::ZodType::object(vec![#tag_field]) },
+                    Some(payload) => quote! {
+                        zod_gen::ZodType::object(vec![
+                            #tag_field,
+                            zod_gen::ZodField::new(#content, #payload),
+                        ])
+                    },
+                };
+                Ok(deprecated_variant(schema, v))
+            }))?;
+
+            TaggedSchema::Schema(
+                quote! { zod_gen::ZodType::discriminated_union(#tag, vec![#(#variant_schemas),*]) },
+            )
+        }
+        // Every variant of an untagged enum is untagged
+        EnumRepresentation::Untagged => unreachable!(),
+    })
 }
 
-#[proc_macro_derive(ZodSchema)]
-pub fn derive_zod_schema(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+/// Builds the output and input schemas of a container from its own fields
+/// or variants, along with its `ZodObjectSchema` impl, if any. A `delegated`
+/// container serializes through another type on one side, so it gets no
+/// impl.
+fn own_bodies(
+    input: &DeriveInput,
+    zod_attrs: &attr::ZodContainer,
+    serde_attrs: &attr::SerdeContainer,
+    delegated: bool,
+) -> syn::Result<(
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+)> {
     let name = &input.ident;
     let name_span = name.span();
 
-    let expanded = match input.data {
-        Data::Struct(data_struct) => match data_struct.fields {
+    // Structs also implement `ZodObjectSchema`, and build their schema from it
+    match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
             Fields::Named(fields_named) => {
-                let fields = fields_named.named.iter().map(|f| {
-                    let ident = f.ident.as_ref().unwrap();
-                    let ident_name =
-                        find_serde_rename_from_attrs(&f.attrs).unwrap_or_else(|| ident.to_string());
-                    let field_name = LitStr::new(&ident_name, ident.span());
-                    let ty = &f.ty;
-                    quote! { (#field_name, <#ty as zod_gen::ZodSchema>::zod_schema().as_str()) }
+                let default = serde_attrs.default.as_ref();
+                // Serde writes the tag, but ignores it when deserializing
+                let tag_field = serde_attrs.struct_tag.as_ref().map(|tag| {
+                    let struct_name = serde_attrs
+                        .rename
+                        .clone()
+                        .unwrap_or_else(|| LitStr::new(&name.unraw().to_string(), name_span));
+                    quote! { zod_gen::ZodField::new(#tag, zod_gen::ZodType::literal(#struct_name)) }
                 });
-                quote! {
-                    impl zod_gen::ZodSchema for #name {
-                        fn zod_schema() -> String {
-                            zod_gen::zod_object(&[#(#fields),*])
-                        }
+                let leading = tag_field.into_iter().collect();
+                let object = |leading, side| {
+                    object_fields(fields_named, leading, side, serde_attrs.rename_all, default)
+                };
+                object(leading, Side::Output).and_then(|output| {
+                    let input = object(Vec::new(), Side::Input)?;
+                    if delegated {
+                        return Ok((output.object_type(), input.object_type(), quote! {}));
                     }
-                }
-            }
-            _ => {
-                return syn::Error::new(
-                    name_span,
-                    "ZodSchema derive only supports structs with named fields",
-                )
-                .to_compile_error()
-                .into();
-            }
-        },
-        Data::Enum(data_enum) => {
-            let representation = parse_enum_serde_attrs(&input.attrs)
-                .expect("Failed to parse serde enum attributes");
-
-            match representation {
-                EnumRepresentation::ExternallyTagged => {
-                    let all_unit = data_enum
-                        .variants
-                        .iter()
-                        .all(|v| matches!(v.fields, Fields::Unit));
-
-                    if all_unit {
-                        let literal_variants: Vec<proc_macro2::TokenStream> = data_enum
-                            .variants
-                            .iter()
-                            .map(|v| {
-                                let renamed = extract_serde_rename_variant(v);
-                                let lit = LitStr::new(&renamed, v.ident.span());
-                                quote! { zod_gen::zod_literal(#lit) }
-                            })
-                            .collect();
+                    let (fields, input_fields) = (&output.fields, &input.fields);
+                    let catchall = output.catchall.as_ref().map(|catchall| {
                         quote! {
-                            impl zod_gen::ZodSchema for #name {
-                                fn zod_schema() -> String {
-                                    let owned: Vec<String> = vec![#(#literal_variants.to_string()),*];
-                                    let refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
-                                    zod_gen::zod_union(&refs)
-                                }
+                            fn catchall() -> Option<zod_gen::ZodType> {
+                                #catchall
                             }
                         }
-                    } else {
-                        let variant_schemas: Vec<proc_macro2::TokenStream> = data_enum.variants.iter().map(|v| {
-                            let renamed = extract_serde_rename_variant(v);
-                            let var_lit = LitStr::new(&renamed, v.ident.span());
-
-                            match &v.fields {
-                                Fields::Unit => {
-                                    quote! {
-                                        zod_gen::zod_literal(#var_lit)
-                                    }
-                                }
-                                Fields::Unnamed(fields) => {
-                                    if fields.unnamed.len() == 1 {
-                                        let field_ty = &fields.unnamed.first().unwrap().ty;
-                                        quote! {
-                                            {
-                                                let lit = zod_gen::zod_literal(#var_lit);
-                                                let payload = <#field_ty as zod_gen::ZodSchema>::zod_schema();
-                                                zod_gen::zod_object(&[(#var_lit, payload.as_str())])
-                                            }
-                                        }
-                                } else {
-                                    let inner_fields: Vec<proc_macro2::TokenStream> = fields.unnamed.iter().map(|f| {
-                                        let field_ty = &f.ty;
-                                        quote! { <#field_ty as zod_gen::ZodSchema>::zod_schema().as_str() }
-                                    }).collect();
-                                        quote! {
-                                            {
-                                                let lit = zod_gen::zod_literal(#var_lit);
-                                                let inner = zod_gen::zod_tuple(&[#(#inner_fields),*]);
-                                                zod_gen::zod_object(&[(#var_lit, inner.as_str())])
-                                            }
-                                        }
-                                    }
-                                }
-                                Fields::Named(fields) => {
-                                    let inner_fields: Vec<proc_macro2::TokenStream> = fields.named.iter().map(|f| {
-                                        let ident = f.ident.as_ref().unwrap();
-                                        let field_name = find_serde_rename_from_attrs(&f.attrs)
-                                            .unwrap_or_else(|| ident.to_string());
-                                        let name_lit = LitStr::new(&field_name, ident.span());
-                                        let field_ty = &f.ty;
-                                        quote! { (#name_lit, <#field_ty as zod_gen::ZodSchema>::zod_schema().as_str()) }
-                                    }).collect();
-                                    quote! {
-                                        {
-                                            let lit = zod_gen::zod_literal(#var_lit);
-                                            let inner = zod_gen::zod_object(&[#(#inner_fields),*]);
-                                            zod_gen::zod_object(&[(#var_lit, inner.as_str())])
-                                        }
-                                    }
-                                }
-                            }
-                        }).collect();
-
+                    });
+                    let input_catchall = input.catchall.as_ref().map(|catchall| {
                         quote! {
-                            impl zod_gen::ZodSchema for #name {
-                                fn zod_schema() -> String {
-                                    let owned: Vec<String> = vec![#(#variant_schemas.to_string()),*];
-                                    let refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
-                                    zod_gen::zod_union(&refs)
-                                }
+                            fn input_catchall() -> Option<zod_gen::ZodType> {
+                                #catchall
                             }
                         }
-                    }
-                }
-                EnumRepresentation::InternallyTagged { tag } => {
-                    let tag_lit = LitStr::new(&tag, name_span);
-
-                    for variant in &data_enum.variants {
-                        match &variant.fields {
-                            Fields::Unnamed(fields) => {
-                                if fields.unnamed.len() != 1 {
-                                    return syn::Error::new_spanned(
-                                        variant,
-                                        "#[serde(tag = \"...\")] cannot be used with tuple variants",
-                                    )
-                                    .to_compile_error()
-                                    .into();
-                                }
+                    });
+                    let object_impl = quote! {
+                        #[allow(deprecated)]
+                        impl zod_gen::ZodObjectSchema for #name {
+                            fn object_fields() -> Vec<zod_gen::ZodField> {
+                                #fields
                             }
-                            Fields::Unit => {}
-                            Fields::Named(_) => {}
-                        }
-                    }
 
-                    let variant_schemas: Vec<proc_macro2::TokenStream> = data_enum.variants.iter().map(|v| {
-                        let renamed = extract_serde_rename_variant(v);
-                        let var_lit = LitStr::new(&renamed, v.ident.span());
+                            #catchall
 
-                        match &v.fields {
-                            Fields::Unit => {
-                                quote! {
-                                    zod_gen::zod_object(&[(#tag_lit, zod_gen::zod_literal(#var_lit).as_str())])
-                                }
-                            }
-                            Fields::Unnamed(fields) => {
-                                if fields.unnamed.len() == 1 {
-                                    let field_ty = &fields.unnamed.first().unwrap().ty;
-                                    let tag_obj = quote! {
-                                        zod_gen::zod_object(&[(#tag_lit, zod_gen::zod_literal(#var_lit).as_str())])
-                                    };
-                                    quote! {
-                                        {
-                                            let tag_obj = #tag_obj;
-                                            let payload = <#field_ty as zod_gen::ZodSchema>::zod_schema();
-                                            zod_gen::zod_intersection(&tag_obj, &payload)
-                                        }
-                                    }
-                                } else {
-                                    #[allow(clippy::useless_conversion, clippy::needless_return)]
-                                    {
-                                        return syn::Error::new_spanned(
-                                            v,
-                                            "#[serde(tag = \"...\")] cannot be used with tuple variants",
-                                        )
-                                        .to_compile_error()
-                                        .into();
-                                    }
-                                }
-                            }
-                            Fields::Named(fields) => {
-                                let inner_fields: Vec<proc_macro2::TokenStream> = fields.named.iter().map(|f| {
-                                    let ident = f.ident.as_ref().unwrap();
-                                    let field_name = find_serde_rename_from_attrs(&f.attrs)
-                                        .unwrap_or_else(|| ident.to_string());
-                                    let name_lit = LitStr::new(&field_name, ident.span());
-                                    let field_ty = &f.ty;
-                                    quote! { (#name_lit, <#field_ty as zod_gen::ZodSchema>::zod_schema().as_str()) }
-                                }).collect();
-                                quote! {
-                                    {
-                                        let lit = zod_gen::zod_literal(#var_lit);
-                                        zod_gen::zod_object(&[(#tag_lit, lit.as_str()), #(#inner_fields),*])
-                                    }
-                                }
-                            }
-                        }
-                    }).collect();
-
-                    quote! {
-                        impl zod_gen::ZodSchema for #name {
-                            fn zod_schema() -> String {
-                                let owned: Vec<String> = vec![#(#variant_schemas.to_string()),*];
-                                let refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
-                                zod_gen::zod_discriminated_union(#tag_lit, &refs)
-                            }
-                        }
-                    }
-                }
-                EnumRepresentation::AdjacentlyTagged { tag, content } => {
-                    let tag_lit = LitStr::new(&tag, name_span);
-                    let content_lit = LitStr::new(&content, name_span);
-
-                    let variant_schemas: Vec<proc_macro2::TokenStream> = data_enum.variants.iter().map(|v| {
-                        let renamed = extract_serde_rename_variant(v);
-                        let var_lit = LitStr::new(&renamed, v.ident.span());
-
-                        match &v.fields {
-                            Fields::Unit => {
-                                quote! {
-                                    zod_gen::zod_object(&[(#tag_lit, zod_gen::zod_literal(#var_lit).as_str())])
-                                }
-                            }
-                            Fields::Unnamed(fields) => {
-                                if fields.unnamed.len() == 1 {
-                                    let field_ty = &fields.unnamed.first().unwrap().ty;
-                                    quote! {
-                                        {
-                                            let lit = zod_gen::zod_literal(#var_lit);
-                                            let payload = <#field_ty as zod_gen::ZodSchema>::zod_schema();
-                                            zod_gen::zod_object(&[(#tag_lit, lit.as_str()), (#content_lit, payload.as_str())])
-                                        }
-                                    }
-                                } else {
-                                    let inner_fields: Vec<proc_macro2::TokenStream> = fields.unnamed.iter().map(|f| {
-                                        let field_ty = &f.ty;
-                                        quote! { <#field_ty as zod_gen::ZodSchema>::zod_schema().as_str() }
-                                    }).collect();
-                                    quote! {
-                                        {
-                                            let lit = zod_gen::zod_literal(#var_lit);
-                                            let payload = zod_gen::zod_tuple(&[#(#inner_fields),*]);
-                                            zod_gen::zod_object(&[(#tag_lit, lit.as_str()), (#content_lit, payload.as_str())])
-                                        }
-                                    }
-                                }
-                            }
-                            Fields::Named(fields) => {
-                                let inner_fields: Vec<proc_macro2::TokenStream> = fields.named.iter().map(|f| {
-                                    let ident = f.ident.as_ref().unwrap();
-                                    let field_name = find_serde_rename_from_attrs(&f.attrs)
-                                        .unwrap_or_else(|| ident.to_string());
-                                    let name_lit = LitStr::new(&field_name, ident.span());
-                                    let field_ty = &f.ty;
-                                    quote! { (#name_lit, <#field_ty as zod_gen::ZodSchema>::zod_schema().as_str()) }
-                                }).collect();
-                                quote! {
-                                    {
-                                        let lit = zod_gen::zod_literal(#var_lit);
-                                        let payload = zod_gen::zod_object(&[#(#inner_fields),*]);
-                                        zod_gen::zod_object(&[(#tag_lit, lit.as_str()), (#content_lit, payload.as_str())])
-                                    }
-                                }
-                            }
-                        }
-                    }).collect();
-
-                    quote! {
-                        impl zod_gen::ZodSchema for #name {
-                            fn zod_schema() -> String {
-                                let owned: Vec<String> = vec![#(#variant_schemas.to_string()),*];
-                                let refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
-                                zod_gen::zod_discriminated_union(#tag_lit, &refs)
-                            }
-                        }
-                    }
-                }
-                EnumRepresentation::Untagged => {
-                    let variant_schemas: Vec<proc_macro2::TokenStream> = data_enum.variants.iter().map(|v| {
-                        match &v.fields {
-                            Fields::Unit => {
-                                quote! { zod_gen::zod_null() }
-                            }
-                            Fields::Unnamed(fields) => {
-                                if fields.unnamed.len() == 1 {
-                                    let field_ty = &fields.unnamed.first().unwrap().ty;
-                                    quote! {
-                                        <#field_ty as zod_gen::ZodSchema>::zod_schema()
-                                    }
-                                } else {
-                                    let inner_fields: Vec<proc_macro2::TokenStream> = fields.unnamed.iter().map(|f| {
-                                        let field_ty = &f.ty;
-                                        quote! { <#field_ty as zod_gen::ZodSchema>::zod_schema().as_str() }
-                                    }).collect();
-                                    quote! {
-                                        zod_gen::zod_tuple(&[#(#inner_fields),*])
-                                    }
-                                }
-                            }
-                            Fields::Named(fields) => {
-                                let inner_fields: Vec<proc_macro2::TokenStream> = fields.named.iter().map(|f| {
-                                    let ident = f.ident.as_ref().unwrap();
-                                    let field_name = find_serde_rename_from_attrs(&f.attrs)
-                                        .unwrap_or_else(|| ident.to_string());
-                                    let name_lit = LitStr::new(&field_name, ident.span());
-                                    let field_ty = &f.ty;
-                                    quote! { (#name_lit, <#field_ty as zod_gen::ZodSchema>::zod_schema().as_str()) }
-                                }).collect();
-                                quote! {
-                                    zod_gen::zod_object(&[#(#inner_fields),*])
-                                }
-                            }
-                        }
-                    }).collect();
-
-                    quote! {
-                        impl zod_gen::ZodSchema for #name {
-                            fn zod_schema() -> String {
-                                let owned: Vec<String> = vec![#(#variant_schemas.to_string()),*];
-                                let refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
-                                zod_gen::zod_union(&refs)
+                            fn input_object_fields() -> Vec<zod_gen::ZodField> {
+                                #input_fields
                             }
+
+                            #input_catchall
                         }
-                    }
-                }
+                    };
+                    Ok((
+                        quote! { <Self as zod_gen::ZodObjectSchema>::object_type() },
+                        quote! { <Self as zod_gen::ZodObjectSchema>::input_object_type() },
+                        object_impl,
+                    ))
+                })
             }
-        }
-        _ => {
-            return syn::Error::new(
+            // Serde writes a newtype struct as its field
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                newtype_schema(fields, Side::Output).and_then(|output| {
+                    Ok((output, newtype_schema(fields, Side::Input)?, quote! {}))
+                })
+            }
+            _ => Err(syn::Error::new(
                 name_span,
-                "ZodSchema derive only supports structs and enums",
-            )
-            .to_compile_error()
-            .into();
+                "ZodSchema derive only supports structs with named fields and newtype structs",
+            )),
+        },
+        Data::Enum(data_enum) if zod_attrs.repr.is_some() => {
+            numeric_enum_body(data_enum).map(|body| (body.clone(), body, quote! {}))
+        }
+        Data::Enum(data_enum) => enum_body(data_enum, serde_attrs, Side::Output).and_then(|body| {
+            let input_body = enum_body(data_enum, serde_attrs, Side::Input)?;
+            Ok((body, input_body, quote! {}))
+        }),
+        Data::Union(_) => Err(syn::Error::new(
+            name_span,
+            "ZodSchema derive only supports structs and enums",
+        )),
+    }
+}
+
+/// The schemas a container delegates to instead of describing its own
+/// fields, as `(output, input)`: its field with `#[serde(transparent)]`, the
+/// `into` type and the `from`/`try_from` type.
+fn delegates(
+    input: &DeriveInput,
+    serde_attrs: &attr::SerdeContainer,
+) -> syn::Result<(
+    Option<proc_macro2::TokenStream>,
+    Option<proc_macro2::TokenStream>,
+)> {
+    if serde_attrs.transparent.is_none() {
+        return Ok((
+            serde_attrs
+                .into
+                .as_ref()
+                .map(|ty| type_schema(ty, Side::Output)),
+            serde_attrs
+                .from
+                .as_ref()
+                .map(|ty| type_schema(ty, Side::Input)),
+        ));
+    }
+    // Same messages and span as serde, so that the duplicates are
+    // deduplicated
+    let fields = match &input.data {
+        Data::Struct(data_struct) if matches!(data_struct.fields, Fields::Unit) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[serde(transparent)] is not allowed on a unit struct",
+            ))
+        }
+        Data::Struct(data_struct) => &data_struct.fields,
+        Data::Enum(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[serde(transparent)] is not allowed on an enum",
+            ))
+        }
+        Data::Union(_) => return Ok((None, None)),
+    };
+    let fields = collect(fields.iter().map(|f| {
+        let (serde, _, ty) = field_type(f)?;
+        Ok((f, serde, ty))
+    }))?;
+    let output = transparent_field(input, &fields, Side::Output);
+    let input = transparent_field(input, &fields, Side::Input);
+    match (output, input) {
+        (Ok(output), Ok(input)) => Ok((Some(output), Some(input))),
+        (Err(mut error), Err(other)) => {
+            error.combine(other);
+            Err(error)
+        }
+        (Err(error), _) | (_, Err(error)) => Err(error),
+    }
+}
+
+/// The schema of the field a `#[serde(transparent)]` struct serializes as on
+/// `side`, checked like serde does: `PhantomData` fields never count.
+fn transparent_field(
+    input: &DeriveInput,
+    fields: &[(&syn::Field, attr::SerdeMember, syn::Type)],
+    side: Side,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut candidates = fields.iter().filter(|(field, serde, _)| {
+        !is_named(&field.ty, "PhantomData")
+            && match side {
+                Side::Output => !serde.skip_serializing,
+                Side::Input => !serde.skip_deserializing && serde.default.is_none(),
+            }
+    });
+    let message = match (candidates.next(), candidates.next()) {
+        (Some((_, _, ty)), None) => return Ok(type_schema(ty, side)),
+        (Some(_), Some(_)) => {
+            "#[serde(transparent)] requires struct to have at most one transparent field"
+        }
+        (None, _) if side == Side::Output => {
+            "#[serde(transparent)] requires at least one field that is not skipped"
+        }
+        (None, _) => {
+            "#[serde(transparent)] requires at least one field that is neither skipped nor has a default"
+        }
+    };
+    Err(syn::Error::new_spanned(input, message))
+}
+
+/// Whether the last path segment of `ty` is `name`, which is how serde
+/// recognizes `Option` and `PhantomData` fields.
+fn is_named(mut ty: &syn::Type, name: &str) -> bool {
+    while let syn::Type::Group(group) = ty {
+        ty = &group.elem;
+    }
+    matches!(ty, syn::Type::Path(path)
+        if path.path.segments.last().is_some_and(|segment| segment.ident == name))
+}
+
+#[proc_macro_derive(ZodSchema, attributes(zod))]
+pub fn derive_zod_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let name_span = name.span();
+
+    let mut errors = Errors::default();
+    let zod_attrs = attr::parse_zod_container(&input.attrs).unwrap_or_else(|error| {
+        errors.push(error);
+        attr::ZodContainer::default()
+    });
+    let serde_attrs = attr::parse_serde_container(
+        &input.attrs,
+        matches!(input.data, Data::Enum(_)),
+        &mut errors,
+    );
+
+    if let (Some(repr), Data::Struct(_) | Data::Union(_)) = (&zod_attrs.repr, &input.data) {
+        errors.push(syn::Error::new_spanned(
+            repr,
+            "#[zod(repr)] is only supported on enums",
+        ));
+    }
+
+    let bodies_and_object_impl = match delegates(input, &serde_attrs) {
+        Ok((Some(output), Some(input))) => Ok((output, input, quote! {})),
+        Ok((None, None)) => own_bodies(input, &zod_attrs, &serde_attrs, false),
+        Ok((output, input_delegate)) => own_bodies(input, &zod_attrs, &serde_attrs, true).map(
+            |(body, input_body, object_impl)| {
+                (
+                    output.unwrap_or(body),
+                    input_delegate.unwrap_or(input_body),
+                    object_impl,
+                )
+            },
+        ),
+        Err(error) => Err(error),
+    };
+    let (body, input_body, object_impl) = bodies_and_object_impl.unwrap_or_else(|error| {
+        errors.push(error);
+        Default::default()
+    });
+    errors.finish()?;
+
+    let name_str = LitStr::new(&name.to_string(), name_span);
+    let export_name = zod_attrs.rename.unwrap_or_else(|| name_str.clone());
+    let (body, input_body) = if zod_attrs.brand {
+        (
+            quote! { zod_gen::ZodType::brand(#body, #export_name) },
+            quote! { zod_gen::ZodType::brand(#input_body, #export_name) },
+        )
+    } else {
+        (body, input_body)
+    };
+    let body = with_meta(body, &zod_attrs.meta);
+    let input_body = with_meta(input_body, &zod_attrs.meta);
+    let registration = if zod_attrs.export {
+        quote! {
+            #[allow(deprecated)]
+            const _: () = {
+                zod_gen::__private::inventory::submit! {
+                    zod_gen::registry::ExportedSchema::new(
+                        #export_name,
+                        <#name as zod_gen::ZodSchema>::zod_type,
+                        <#name as zod_gen::ZodSchema>::zod_input_type,
+                    )
+                }
+            };
         }
+    } else {
+        quote! {}
     };
+    let named = |body| {
+        with_deprecation(
+            quote! {
+                zod_gen::ZodType::named(
+                    concat!(module_path!(), "::", #name_str),
+                    #export_name,
+                    #body,
+                )
+            },
+            &zod_attrs.meta.deprecated,
+        )
+    };
+    let (named_body, named_input_body) = (named(body), named(input_body));
+    // Deprecated types and variants may be named in the generated code
+    Ok(quote! {
+        #[allow(deprecated)]
+        impl zod_gen::ZodSchema for #name {
+            fn zod_schema() -> String {
+                <Self as zod_gen::ZodSchema>::zod_type().to_zod()
+            }
+
+            fn zod_type() -> zod_gen::ZodType {
+                #named_body
+            }
+
+            fn zod_input_type() -> zod_gen::ZodType {
+                #named_input_body
+            }
+        }
+
+        #object_impl
 
-    TokenStream::from(expanded)
+        #registration
+    })
 }
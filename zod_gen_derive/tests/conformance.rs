@@ -0,0 +1,136 @@
+//! Round-trip tests: values serialized by serde must pass their derived
+//! schema's validation, for every enum representation.
+
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use zod_gen::testing::{assert_conforms, check_conforms, ConformanceError};
+use zod_gen_derive::ZodSchema;
+
+#[derive(Debug, Arbitrary, ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Account {
+    id: u32,
+    #[serde(rename = "displayName")]
+    display_name: String,
+    email: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<Role>,
+    labels: HashMap<String, i16>,
+    #[serde(flatten)]
+    audit: Audit,
+    status: Status,
+    event: Event,
+    change: Change,
+    value: Scalar,
+    priority: Priority,
+}
+
+#[derive(Debug, Arbitrary, ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Audit {
+    #[serde(rename = "createdBy")]
+    created_by: String,
+    revision: u8,
+}
+
+/// Externally tagged, unit variants only
+#[derive(Debug, Arbitrary, ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Role {
+    #[serde(rename = "admin")]
+    Admin,
+    #[serde(rename = "member")]
+    Member,
+}
+
+/// Externally tagged
+#[derive(Debug, Arbitrary, ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Status {
+    Active,
+    Suspended(String),
+    Moved(u32, String),
+    Closed { reason: Option<String> },
+}
+
+/// Internally tagged
+#[derive(Debug, Arbitrary, ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum Event {
+    Ping,
+    Login(Audit),
+    Logout { forced: bool },
+}
+
+/// Adjacently tagged
+#[derive(Debug, Arbitrary, ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data")]
+#[allow(dead_code)]
+enum Change {
+    Reset,
+    Rename(String),
+    Resize(u16, u16),
+    Move { x: i32, y: i32 },
+}
+
+/// Untagged
+#[derive(Debug, Arbitrary, ZodSchema, Serialize, Deserialize)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum Scalar {
+    Flag(bool),
+    Count(i64),
+    Text(String),
+    Nothing,
+}
+
+#[derive(Debug, Arbitrary, ZodSchema, Serialize_repr, Deserialize_repr)]
+#[zod(repr)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Priority {
+    Low = 1,
+    High = 5,
+}
+
+/// Describes a number as a string, which its serialization isn't.
+#[derive(ZodSchema, Serialize)]
+#[allow(dead_code)]
+struct Mislabeled {
+    #[zod(as = String)]
+    count: u32,
+}
+
+proptest! {
+    #[test]
+    fn test_accounts_conform(account in any::<Account>()) {
+        assert_conforms::<Account>(&account);
+    }
+
+    #[test]
+    fn test_events_conform(event in any::<Event>(), change in any::<Change>()) {
+        assert_conforms::<Event>(&event);
+        assert_conforms::<Change>(&change);
+    }
+}
+
+#[test]
+fn test_mismatch_is_explained() {
+    let err = check_conforms::<Mislabeled>(&Mislabeled { count: 3 }).unwrap_err();
+    assert!(matches!(err, ConformanceError::Mismatch { .. }));
+    assert_eq!(
+        err.to_string(),
+        "the Zod schema of `conformance::Mislabeled` rejects its serialized JSON:\n  - Invalid input: expected string, received number at count\nJSON:\n{\n  \"count\": 3\n}\nschema:\nz.object({\n  count: z.string()\n})"
+    );
+}
+
+#[test]
+#[should_panic(expected = "expected string, received number at count")]
+fn test_assert_conforms_panics() {
+    assert_conforms::<Mislabeled>(&Mislabeled { count: 3 });
+}
@@ -0,0 +1,113 @@
+//! Tests for the `.default(...)` values of fields serde fills in when they
+//! are missing.
+
+use serde::{Deserialize, Serialize};
+use zod_gen::{ZodGenerator, ZodSchema};
+use zod_gen_derive::ZodSchema;
+
+fn default_language() -> String {
+    "en".to_string()
+}
+
+/// Deserialize-only, so its default can't be serialized.
+#[derive(Default, Deserialize, ZodSchema)]
+#[allow(dead_code)]
+struct Opaque {
+    level: u8,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Preferences {
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default = "default_language")]
+    language: String,
+    #[serde(default)]
+    nickname: Option<String>,
+    #[serde(default)]
+    #[zod(default = "UTC")]
+    timezone: String,
+    #[serde(default)]
+    #[zod(as = String)]
+    limit: u32,
+}
+
+#[derive(ZodSchema, Deserialize)]
+#[allow(dead_code)]
+struct Query {
+    #[serde(default)]
+    opaque: Opaque,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(dead_code)]
+struct Window {
+    width: u32,
+    #[serde(rename = "fullScreen")]
+    full_screen: bool,
+    #[serde(default = "default_language")]
+    title: String,
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Window {
+            width: 800,
+            full_screen: true,
+            title: String::new(),
+        }
+    }
+}
+
+#[test]
+fn test_field_defaults() {
+    assert_eq!(
+        Preferences::zod_input_schema(),
+        "z.object({\n  tags: z.array(z.string()).default([]),\n  language: z.string().default(\"en\"),\n  nickname: z.string().nullable().default(null),\n  timezone: z.string().default(\"UTC\"),\n  limit: z.string().optional()\n})"
+    );
+    // Defaults only apply when parsing
+    assert_eq!(
+        Preferences::zod_schema(),
+        "z.object({\n  tags: z.array(z.string()),\n  language: z.string(),\n  nickname: z.string().nullable(),\n  timezone: z.string(),\n  limit: z.string()\n})"
+    );
+}
+
+#[test]
+fn test_unserializable_default_is_optional() {
+    assert_eq!(
+        Query::zod_input_schema(),
+        "z.object({\n  opaque: z.object({\n  level: z.number()\n}).optional()\n})"
+    );
+}
+
+#[test]
+fn test_container_defaults() {
+    // Field defaults take precedence over the container's, as in serde
+    assert_eq!(
+        Window::zod_input_schema(),
+        "z.object({\n  width: z.number().default(800),\n  fullScreen: z.boolean().default(true),\n  title: z.string().default(\"en\")\n})"
+    );
+}
+
+#[test]
+fn test_defaults_in_declarations_and_json_schema() {
+    let mut generator = ZodGenerator::new();
+    generator.add::<Window>().unwrap();
+    assert!(generator
+        .generate_declarations()
+        .contains("export interface WindowInput {\n  width: number;\n"));
+
+    let json = generator.generate_json_schema();
+    assert_eq!(
+        json["$defs"]["WindowInput"]["properties"]["width"]["default"],
+        800
+    );
+    assert_eq!(
+        json["$defs"]["WindowInput"]["required"]
+            .as_array()
+            .map(Vec::len),
+        Some(0)
+    );
+}
@@ -0,0 +1,164 @@
+//! Tests for containers that serialize through another type:
+//! `#[serde(transparent)]`, `from`, `try_from` and `into`.
+
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+use zod_gen::{ZodGenerator, ZodSchema};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[allow(dead_code)]
+struct UserId(u64);
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[zod(brand)]
+#[allow(dead_code)]
+struct OrgId(String);
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[zod(brand, rename = "MemberId")]
+#[allow(dead_code)]
+struct Member(String);
+
+/// A plain newtype struct, which serde writes as its field too
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[zod(brand)]
+#[allow(dead_code)]
+struct TeamId(String);
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Membership {
+    org: OrgId,
+    members: Vec<Member>,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[allow(dead_code)]
+struct Tags {
+    tags: Vec<String>,
+    #[serde(skip)]
+    cached_len: usize,
+}
+
+/// Like serde, `PhantomData` fields never count as the transparent field
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[allow(dead_code)]
+struct Tagged {
+    value: String,
+    marker: PhantomData<u64>,
+}
+
+#[derive(Clone, ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct RgbParts {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[derive(Clone, ZodSchema, Serialize, Deserialize)]
+#[serde(from = "RgbParts", into = "String")]
+#[allow(dead_code)]
+struct Color(u32);
+
+impl From<RgbParts> for Color {
+    fn from(parts: RgbParts) -> Self {
+        Color(u32::from_be_bytes([0, parts.r, parts.g, parts.b]))
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        format!("#{:06x}", color.0)
+    }
+}
+
+#[derive(Clone, ZodSchema, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+#[allow(dead_code)]
+struct Slug {
+    value: String,
+}
+
+impl TryFrom<String> for Slug {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Slug { value })
+    }
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Profile {
+    id: UserId,
+    color: Color,
+}
+
+#[test]
+fn test_transparent() {
+    assert_eq!(UserId::zod_schema(), "z.number()");
+    assert_eq!(UserId::zod_input_schema(), "z.number()");
+    assert_eq!(Tags::zod_schema(), "z.array(z.string())");
+    assert_eq!(Tagged::zod_schema(), "z.string()");
+    assert_eq!(Tagged::zod_input_schema(), "z.string()");
+}
+
+#[test]
+fn test_brand() {
+    assert_eq!(OrgId::zod_schema(), "z.string().brand<\"OrgId\">()");
+    assert_eq!(Member::zod_schema(), "z.string().brand<\"MemberId\">()");
+    assert_eq!(TeamId::zod_schema(), "z.string().brand<\"TeamId\">()");
+    assert_eq!(TeamId::zod_input_schema(), "z.string().brand<\"TeamId\">()");
+
+    let mut generator = ZodGenerator::new();
+    generator.add::<Membership>().unwrap();
+    assert!(generator
+        .generate()
+        .contains("export const OrgIdSchema = z.string().brand<\"OrgId\">();\n"));
+    let declarations = generator.generate_declarations();
+    assert!(declarations.contains("export type OrgId = string & { readonly __brand: 'OrgId' };\n"));
+    assert!(declarations
+        .contains("export type MemberId = string & { readonly __brand: 'MemberId' };\n"));
+    assert!(declarations.contains("  org: OrgId;\n"));
+    assert_eq!(
+        generator.generate_json_schema()["$defs"]["OrgId"]["type"],
+        "string"
+    );
+}
+
+#[test]
+fn test_from_and_into() {
+    assert_eq!(Color::zod_schema(), "z.string()");
+    assert_eq!(
+        Color::zod_input_schema(),
+        "z.object({\n  r: z.number(),\n  g: z.number(),\n  b: z.number()\n})"
+    );
+}
+
+#[test]
+fn test_try_from_only_changes_input() {
+    assert_eq!(Slug::zod_schema(), "z.object({\n  value: z.string()\n})");
+    assert_eq!(Slug::zod_input_schema(), "z.string()");
+}
+
+#[test]
+fn test_delegating_types_keep_their_name() {
+    let mut generator = ZodGenerator::new();
+    generator.add::<Profile>().unwrap();
+    let declarations = generator.generate_declarations();
+    assert!(declarations.contains("export type UserId = number;\n"));
+    assert!(declarations.contains("export type ColorOutput = string;\n"));
+    // The proxy type is a dependency like any other
+    assert!(declarations.contains("export type ColorInput = RgbParts;\n"));
+    assert!(declarations.contains("export interface RgbParts {\n"));
+    assert!(declarations
+        .contains("export interface ProfileInput {\n  id: UserId;\n  color: ColorInput;\n}"));
+}
@@ -0,0 +1,105 @@
+//! Tests for `#[deprecated]` fields, variants and types.
+
+use serde::{Deserialize, Serialize};
+use zod_gen::{ZodGenerator, ZodSchema};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Account {
+    id: String,
+    #[deprecated(since = "1.2.0", note = "use `id` instead")]
+    legacy_id: Option<u32>,
+    #[deprecated]
+    nickname: String,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Plan {
+    Free,
+    #[deprecated = "no longer sold"]
+    Legacy,
+    Pro,
+}
+
+#[derive(ZodSchema)]
+#[zod(repr)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Priority {
+    Low = 1,
+    #[deprecated]
+    Medium = 2,
+    High = 3,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum Event {
+    Created {
+        id: String,
+    },
+    #[deprecated(note = "use Created")]
+    Registered {
+        id: String,
+    },
+}
+
+#[deprecated(note = "use Account")]
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[zod(export)]
+#[allow(dead_code)]
+struct User {
+    name: String,
+}
+
+#[test]
+fn test_deprecated_fields() {
+    assert_eq!(
+        Account::zod_schema(),
+        "z.object({\n  id: z.string(),\n  /** @deprecated use `id` instead */\n  legacy_id: z.number().nullable().meta({\"deprecated\":true}),\n  /** @deprecated */\n  nickname: z.string().meta({\"deprecated\":true})\n})"
+    );
+
+    let mut generator = ZodGenerator::new();
+    generator.add::<Account>().unwrap();
+    assert!(generator.generate_declarations().contains(
+        "export interface AccountOutput {\n  id: string;\n  /** @deprecated use `id` instead */\n  legacy_id: number | null;\n  /** @deprecated */\n  nickname: string;\n}"
+    ));
+    let schema = generator.generate_json_schema();
+    let properties = &schema["$defs"]["AccountOutput"]["properties"];
+    assert_eq!(properties["legacy_id"]["deprecated"], true);
+    assert!(properties["id"].get("deprecated").is_none());
+}
+
+#[test]
+fn test_deprecated_variants() {
+    let mut generator = ZodGenerator::new();
+    generator.enum_constants(true).add::<Plan>().unwrap();
+    assert!(generator.generate().contains(
+        "export const Plan = {\n  Free: 'Free',\n  /** @deprecated no longer sold */\n  Legacy: 'Legacy',\n  Pro: 'Pro',\n} as const;"
+    ));
+
+    generator.add::<Priority>().unwrap();
+    assert!(generator
+        .generate()
+        .contains("  Low: 1,\n  /** @deprecated */\n  Medium: 2,\n"));
+
+    assert!(Event::zod_schema().contains(
+        "z.object({\n  type: z.literal('Registered'),\n  id: z.string()\n}).meta({\"deprecated\":true})"
+    ));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_types() {
+    let mut generator = ZodGenerator::new();
+    generator.add::<User>().unwrap();
+    assert!(generator.generate().contains(
+        "/** @deprecated use Account */\nexport const UserSchema = z.object({\n  name: z.string()\n}).meta({\"deprecated\":true});\n/** @deprecated use Account */\nexport type User = z.infer<typeof UserSchema>;"
+    ));
+    assert!(generator
+        .generate_declarations()
+        .contains("/** @deprecated use Account */\nexport interface User {"));
+}
@@ -1,5 +1,6 @@
-use serde::Serialize;
-use zod_gen::ZodSchema as _;
+use serde::{Deserialize, Serialize};
+use zod_gen::openapi::{OpenApi, Operation};
+use zod_gen::{ZodGenerator, ZodSchema as _};
 use zod_gen_derive::ZodSchema;
 
 #[derive(ZodSchema)]
@@ -54,8 +55,7 @@ fn test_small_integer_struct_schema() {
 #[test]
 fn test_enum_schema() {
     let schema = TestEnum::zod_schema();
-    assert!(schema.contains("z.literal('Foo')"));
-    assert!(schema.contains("z.literal('Bar')"));
+    assert_eq!(schema, "z.enum(['Foo', 'Bar'])");
 }
 
 #[test]
@@ -80,3 +80,224 @@ fn test_struct_rename() {
     let schema = TestStructWithRename::zod_schema();
     assert!(schema.contains("FOOBAR: z.string()"));
 }
+
+#[derive(ZodSchema, Serialize)]
+#[allow(dead_code)]
+struct TestHeaders {
+    #[serde(rename = "content-type")]
+    content_type: String,
+    #[deprecated]
+    #[serde(rename = "x-request-id")]
+    request_id: String,
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_keys_that_are_not_identifiers_are_quoted() {
+    assert_eq!(
+        TestHeaders::zod_schema(),
+        "z.object({\n  \"content-type\": z.string(),\n  /** @deprecated */\n  \"x-request-id\": z.string().meta({\"deprecated\":true})\n})"
+    );
+
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<TestHeaders>("Headers");
+    assert!(gen.generate_declarations().contains(
+        "export interface Headers {\n  \"content-type\": string;\n  /** @deprecated */\n  \"x-request-id\": string;\n}"
+    ));
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct TestStructWithSerdeOptions {
+    #[serde(rename(serialize = "userId", deserialize = "user_id"), alias = "uid")]
+    user_id: u32,
+    // Deserialize-only options leave the serialized shape alone
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_struct_serde_options() {
+    let schema = TestStructWithSerdeOptions::zod_schema();
+    assert_eq!(
+        schema,
+        "z.object({\n  userId: z.number(),\n  tags: z.array(z.string())\n})"
+    );
+}
+
+fn serialize_as_string<S: serde::Serializer>(
+    value: &u64,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+struct Cents(u64);
+
+fn serialize_cents<S: serde::Serializer>(
+    value: &[Cents],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(value.iter().map(|cents| cents.0))
+}
+
+#[derive(ZodSchema, Serialize)]
+#[allow(dead_code)]
+struct TestStructWithOverrides {
+    #[zod(as = String)]
+    #[serde(serialize_with = "serialize_as_string")]
+    balance: u64,
+    #[zod(as = Vec<u64>)]
+    #[serde(serialize_with = "serialize_cents")]
+    history: Vec<Cents>,
+}
+
+fn deserialize_from_string<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct TestStructWithInputOverride {
+    #[zod(as = String)]
+    #[serde(deserialize_with = "deserialize_from_string")]
+    balance: u64,
+}
+
+#[test]
+fn test_field_schema_override() {
+    let schema = TestStructWithOverrides::zod_schema();
+    assert!(schema.contains("balance: z.string()"), "schema: {schema}");
+    assert!(
+        schema.contains("history: z.array(z.number())"),
+        "schema: {schema}"
+    );
+    assert_eq!(
+        TestStructWithInputOverride::zod_input_schema(),
+        "z.object({\n  balance: z.string()\n})"
+    );
+}
+
+#[derive(ZodSchema)]
+#[allow(dead_code)]
+struct TestOwner {
+    name: String,
+    pet: Option<TestPet>,
+    status: TestEnum,
+}
+
+#[derive(ZodSchema)]
+#[allow(dead_code)]
+struct TestPet {
+    age: u8,
+}
+
+#[test]
+fn test_declarations_reference_registered_types() {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<TestOwner>("Owner");
+    gen.add_schema::<TestPet>("Pet");
+    let output = gen.generate_declarations();
+    assert!(
+        output.contains(
+            "export interface OwnerOutput {\n  name: string;\n  pet: Pet | null;\n  status: TestEnum;\n}"
+        ),
+        "output: {output}"
+    );
+    assert!(
+        output.contains("export interface Pet {\n  age: number;\n}"),
+        "output: {output}"
+    );
+    // Dependencies that were not added explicitly use their Rust type name
+    assert!(
+        output.contains("export type TestEnum = 'Foo' | 'Bar';"),
+        "output: {output}"
+    );
+    assert!(!output.contains("TestPet"), "output: {output}");
+}
+
+#[test]
+fn test_json_schema_references_registered_types() {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<TestOwner>("Owner");
+    gen.add_schema::<TestPet>("Pet");
+    let output = gen.generate_json_schema();
+    let owner = &output["$defs"]["OwnerOutput"];
+    assert_eq!(
+        owner["properties"]["pet"]["anyOf"][0]["$ref"], "#/$defs/Pet",
+        "output: {output}"
+    );
+    assert_eq!(
+        owner["properties"]["status"]["$ref"], "#/$defs/TestEnum",
+        "output: {output}"
+    );
+    assert_eq!(
+        output["$defs"]["TestEnum"]["enum"][0], "Foo",
+        "output: {output}"
+    );
+    assert_eq!(
+        output["$defs"]["Pet"]["properties"]["age"]["type"], "number",
+        "output: {output}"
+    );
+}
+
+#[test]
+fn test_openapi_references_components() {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<TestOwner>("Owner");
+    gen.add_schema::<TestPet>("Pet");
+
+    let mut api = OpenApi::new("Owners", "1.0.0");
+    api.operation(
+        Operation::post("/owners")
+            .request::<TestOwner>()
+            .response::<Vec<TestOwner>>(200, "All owners"),
+    );
+    let output = api.to_json(&gen);
+
+    let post = &output["paths"]["/owners"]["post"];
+    assert_eq!(
+        post["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/OwnerInput",
+        "output: {output}"
+    );
+    assert_eq!(
+        post["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+        "#/components/schemas/OwnerOutput",
+        "output: {output}"
+    );
+    assert_eq!(
+        output["components"]["schemas"]["OwnerOutput"]["properties"]["pet"]["anyOf"][0]["$ref"],
+        "#/components/schemas/Pet",
+        "output: {output}"
+    );
+}
+
+#[test]
+fn test_add_schema_exports_transitive_dependencies() {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<Vec<TestOwner>>("Owners");
+    let output = gen.generate();
+    assert!(
+        output.contains("export const OwnersInputSchema = "),
+        "output: {output}"
+    );
+    assert!(
+        output.contains("export const TestOwnerOutputSchema = "),
+        "output: {output}"
+    );
+    assert!(
+        output.contains("export const TestPetSchema = "),
+        "output: {output}"
+    );
+    assert!(
+        output.contains("export const TestEnumSchema = "),
+        "output: {output}"
+    );
+    assert_eq!(output.matches("export type TestPet ").count(), 1);
+}
@@ -8,6 +8,7 @@
 //! - Internally tagged (`#[serde(tag = "...")]`)
 //! - Adjacently tagged (`#[serde(tag = "...", content = "...")]`)
 //! - Untagged (`#[serde(untagged)]`)
+//! - Numeric (`serde_repr` with `#[zod(repr)]`)
 //!
 //! Key invariants:
 //! - Default behavior matches Serde's actual JSON (externally tagged).
@@ -15,11 +16,12 @@
 //! - No implicit default tag key; if `#[serde(tag=...)]` exists, use exactly that string.
 
 use serde::{Deserialize, Serialize};
-use zod_gen::ZodSchema as _;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use zod_gen::{ZodGenerator, ZodSchema as _};
 use zod_gen_derive::ZodSchema;
 
 // ============================================================================
-// BACKWARD COMPATIBILITY: All-unit enums should keep producing union of literals
+// ALL-UNIT ENUMS: a z.enum of the serialized names
 // ============================================================================
 
 #[derive(ZodSchema, Serialize, Deserialize)]
@@ -31,13 +33,90 @@ enum Status {
 }
 
 #[test]
-fn test_backward_compat_unit_only_enum() {
+fn test_unit_only_enum_is_z_enum() {
     let schema = Status::zod_schema();
-    // Must contain literal unions for unit variants
-    assert!(schema.contains("z.literal('Active')"), "schema: {schema}");
-    assert!(schema.contains("z.literal('inactive')"), "schema: {schema}");
-    // Must be a union
-    assert!(schema.contains("z.union(["), "schema: {schema}");
+    assert_eq!(schema, "z.enum(['Active', 'inactive'])");
+}
+
+#[test]
+fn test_unit_only_enum_constants() {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<Status>("Status");
+    assert!(!gen.generate().contains("export const Status ="));
+
+    gen.enum_constants(true);
+    let output = gen.generate();
+    assert!(
+        output.contains(
+            "export const StatusSchema = z.enum(['Active', 'inactive']);\n\
+             export type Status = z.infer<typeof StatusSchema>;\n\
+             export const Status = {\n  Active: 'Active',\n  Inactive: 'inactive',\n} as const;\n"
+        ),
+        "output: {output}"
+    );
+
+    assert!(gen
+        .generate_declarations()
+        .contains("export type Status = 'Active' | 'inactive';"));
+    let json = gen.generate_json_schema();
+    assert_eq!(json["$defs"]["Status"]["enum"][0], "Active", "json: {json}");
+    assert_eq!(
+        json["$defs"]["Status"]["enum"][1], "inactive",
+        "json: {json}"
+    );
+}
+
+// ============================================================================
+// NUMERIC ENUMS: serialized as their discriminant
+// ============================================================================
+
+#[derive(ZodSchema, Serialize_repr, Deserialize_repr)]
+#[zod(repr)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Priority {
+    Low,
+    High = 10,
+    // Implicit discriminants continue from the previous one
+    Urgent,
+}
+
+#[derive(ZodSchema, Serialize_repr)]
+#[zod(repr)]
+#[repr(i8)]
+#[allow(dead_code)]
+enum Offset {
+    Back = -1,
+}
+
+#[test]
+fn test_numeric_enum_uses_discriminants() {
+    assert_eq!(
+        Priority::zod_schema(),
+        "z.union([z.literal(0), z.literal(10), z.literal(11)])"
+    );
+    assert_eq!(Offset::zod_schema(), "z.literal(-1)");
+}
+
+#[test]
+fn test_numeric_enum_constants() {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<Priority>("Priority");
+    let output = gen.generate();
+    assert!(
+        output.contains(
+            "export type Priority = z.infer<typeof PrioritySchema>;\n\
+             export const Priority = {\n  Low: 0,\n  High: 10,\n  Urgent: 11,\n} as const;\n"
+        ),
+        "output: {output}"
+    );
+
+    assert!(gen
+        .generate_declarations()
+        .contains("export type Priority = 0 | 10 | 11;"));
+    let json = gen.generate_json_schema();
+    assert_eq!(json["$defs"]["Priority"]["type"], "integer", "json: {json}");
+    assert_eq!(json["$defs"]["Priority"]["enum"][2], 11, "json: {json}");
 }
 
 // ============================================================================
@@ -281,8 +360,31 @@ fn test_internal_newtype_struct() {
     assert!(schema.contains("x: z.number()"), "schema: {schema}");
     // Must NOT have a "data" wrapper
     assert!(!schema.contains(r#""data""#), "schema: {schema}");
-    // Should use intersection for flattening
-    assert!(schema.contains("z.intersection("), "schema: {schema}");
+    // Tag and payload fields are merged into one object, since Zod requires
+    // discriminated union members to be objects
+    assert_eq!(
+        schema,
+        "z.discriminatedUnion('type', [z.object({\n  type: z.literal('Payload'),\n  x: z.number()\n})])"
+    );
+}
+
+#[test]
+fn test_internal_json_schema_one_of_with_const_tags() {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<InternalStructUnit>("InternalStructUnit");
+    let output = gen.generate_json_schema();
+    let variants = output["$defs"]["InternalStructUnit"]["oneOf"]
+        .as_array()
+        .expect("discriminated union renders as oneOf");
+    assert_eq!(variants.len(), 2, "output: {output}");
+    assert_eq!(
+        variants[0]["properties"]["type"]["const"], "Request",
+        "output: {output}"
+    );
+    assert_eq!(
+        variants[1]["properties"]["type"]["const"], "Response",
+        "output: {output}"
+    );
 }
 
 // ============================================================================
@@ -403,6 +505,141 @@ fn test_untagged_tuple_unit() {
     assert!(schema.contains("z.null()"), "schema: {schema}");
 }
 
+// ============================================================================
+// UNTAGGED VARIANTS AND #[serde(other)]
+// ============================================================================
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Reading {
+    Celsius(f64),
+    Missing,
+    #[serde(untagged)]
+    Raw(String),
+}
+
+#[test]
+fn test_untagged_variant_joins_union_as_payload() {
+    let schema = Reading::zod_schema();
+    assert_eq!(
+        schema,
+        "z.union([z.object({\n  Celsius: z.number()\n}), z.literal('Missing'), z.string()])"
+    );
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+#[allow(dead_code)]
+enum Event {
+    Login {
+        user: String,
+    },
+    #[serde(untagged)]
+    Legacy(Vec<String>),
+    #[serde(untagged)]
+    Nothing,
+}
+
+#[test]
+fn test_untagged_variants_of_tagged_enum() {
+    let schema = Event::zod_schema();
+    assert!(
+        schema.starts_with("z.union([z.discriminatedUnion('kind', ["),
+        "schema: {schema}"
+    );
+    assert!(
+        schema.ends_with("]), z.array(z.string()), z.null()])"),
+        "schema: {schema}"
+    );
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum Notification {
+    Message {
+        text: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Channel {
+    Email,
+    Sms,
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+#[test]
+fn test_other_variant_catches_unknown_tags() {
+    let schema = Notification::zod_input_schema();
+    assert!(
+        schema.starts_with("z.union([z.discriminatedUnion('type', ["),
+        "schema: {schema}"
+    );
+    // Only unknown tags fall back, the known variants stay checked
+    assert!(
+        schema.ends_with(
+            "z.object({\n  type: z.string().refine((value) => !['Message', 'Unknown'].includes(value))\n})])"
+        ),
+        "schema: {schema}"
+    );
+
+    // Serde only falls back to the variant when deserializing
+    assert!(
+        !Notification::zod_schema().contains(".refine("),
+        "schema: {}",
+        Notification::zod_schema()
+    );
+
+    assert_eq!(
+        Channel::zod_input_schema(),
+        "z.union([z.enum(['Email', 'Sms', 'unknown']), \
+         z.string().refine((value) => !['Email', 'Sms', 'unknown'].includes(value))])"
+    );
+    assert_eq!(Channel::zod_schema(), "z.enum(['Email', 'Sms', 'unknown'])");
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<Channel>("Channel");
+    let declarations = gen.generate_declarations();
+    assert!(
+        declarations.contains("export type ChannelInput = 'Email' | 'Sms' | 'unknown' | string;")
+    );
+    assert!(declarations.contains("export type ChannelOutput = 'Email' | 'Sms' | 'unknown';"));
+}
+
+#[test]
+fn test_other_variant_validates_known_tags() {
+    let schema = Notification::zod_input_type();
+    for (payload, valid) in [
+        (serde_json::json!({ "type": "Message", "text": "hi" }), true),
+        (serde_json::json!({ "type": "Unknown" }), true),
+        (serde_json::json!({ "type": "Reminder", "at": 5 }), true),
+        // A known tag with an invalid payload doesn't fall back
+        (serde_json::json!({ "type": "Message", "text": 5 }), false),
+        (serde_json::json!({ "type": "Message" }), false),
+        (serde_json::json!({ "type": 5 }), false),
+    ] {
+        assert_eq!(
+            schema.validate(&payload).is_ok(),
+            valid,
+            "payload: {payload}"
+        );
+        assert_eq!(
+            serde_json::from_value::<Notification>(payload.clone()).is_ok(),
+            valid,
+            "payload: {payload}"
+        );
+    }
+
+    let channel = Channel::zod_input_type();
+    assert!(channel.validate(&serde_json::json!("Fax")).is_ok());
+    assert!(serde_json::from_value::<Channel>(serde_json::json!("Fax")).is_ok());
+    assert!(channel.validate(&serde_json::json!(5)).is_err());
+}
+
 // ============================================================================
 // EDGE CASES
 // ============================================================================
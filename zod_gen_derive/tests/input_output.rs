@@ -0,0 +1,309 @@
+//! Tests for the separate input (deserialize) and output (serialize) schemas.
+
+use serde::{Deserialize, Serialize};
+use zod_gen::openapi::{OpenApi, Operation};
+use zod_gen::{ZodGenerator, ZodSchema};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Settings {
+    #[serde(default)]
+    theme: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    #[serde(rename(serialize = "userId", deserialize = "user_id"))]
+    user_id: u32,
+    #[serde(skip_serializing)]
+    password: String,
+    #[serde(skip_deserializing)]
+    version: u32,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(dead_code)]
+struct Paging {
+    page: u32,
+    per_page: u32,
+}
+
+impl Default for Paging {
+    fn default() -> Self {
+        Paging {
+            page: 1,
+            per_page: 20,
+        }
+    }
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Profile {
+    settings: Settings,
+    origin: Point,
+    history: Vec<Settings>,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum Command {
+    Rename {
+        #[serde(default)]
+        name: String,
+    },
+    #[serde(skip_deserializing)]
+    Internal,
+}
+
+#[test]
+fn test_struct_sides() {
+    assert_eq!(
+        Settings::zod_schema(),
+        "z.object({\n  theme: z.string(),\n  nickname: z.string().nullable().optional(),\n  userId: z.number(),\n  version: z.number()\n})"
+    );
+    assert_eq!(
+        Settings::zod_input_schema(),
+        "z.object({\n  theme: z.string().default(\"\"),\n  nickname: z.string().nullable().optional(),\n  user_id: z.number(),\n  password: z.string()\n})"
+    );
+}
+
+/// Serde reads a missing `Option` field as `None`, unless a custom function
+/// deserializes it
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Signup {
+    email: String,
+    referrer: Option<String>,
+    #[serde(deserialize_with = "Option::deserialize")]
+    #[zod(as = Option<String>)]
+    coupon: Option<String>,
+}
+
+#[test]
+fn test_missing_option_fields() {
+    assert_eq!(
+        Signup::zod_input_schema(),
+        "z.object({\n  email: z.string(),\n  referrer: z.string().nullable().optional(),\n  coupon: z.string().nullable()\n})"
+    );
+    assert_eq!(
+        Signup::zod_schema(),
+        "z.object({\n  email: z.string(),\n  referrer: z.string().nullable(),\n  coupon: z.string().nullable()\n})"
+    );
+
+    let payload = serde_json::json!({ "email": "a@b.c", "coupon": null });
+    let signup: Signup = serde_json::from_value(payload.clone()).unwrap();
+    assert_eq!(signup.referrer, None);
+    assert!(Signup::zod_input_type().validate(&payload).is_ok());
+
+    // Both sides reject a missing `coupon`
+    let payload = serde_json::json!({ "email": "a@b.c" });
+    assert!(serde_json::from_value::<Signup>(payload.clone()).is_err());
+    assert!(Signup::zod_input_type().validate(&payload).is_err());
+
+    let mut generator = ZodGenerator::new();
+    generator.add::<Signup>().unwrap();
+    assert!(generator.generate_declarations().contains(
+        "export interface SignupInput {\n  email: string;\n  referrer?: string | null;\n"
+    ));
+    let json = generator.generate_json_schema();
+    assert_eq!(
+        json["$defs"]["SignupInput"]["required"],
+        serde_json::json!(["email", "coupon"])
+    );
+}
+
+#[test]
+fn test_container_default() {
+    assert_eq!(
+        Paging::zod_schema(),
+        "z.object({\n  page: z.number(),\n  per_page: z.number()\n})"
+    );
+    assert_eq!(
+        Paging::zod_input_schema(),
+        "z.object({\n  page: z.number().default(1),\n  per_page: z.number().default(20)\n})"
+    );
+}
+
+#[test]
+fn test_same_sides() {
+    assert_eq!(Point::zod_input_type(), Point::zod_type());
+}
+
+#[test]
+fn test_enum_sides() {
+    assert_eq!(
+        Command::zod_schema(),
+        "z.discriminatedUnion('type', [z.object({\n  type: z.literal('Rename'),\n  name: z.string()\n}), z.object({\n  type: z.literal('Internal')\n})])"
+    );
+    assert_eq!(
+        Command::zod_input_schema(),
+        "z.discriminatedUnion('type', [z.object({\n  type: z.literal('Rename'),\n  name: z.string().default(\"\")\n})])"
+    );
+}
+
+#[test]
+fn test_generator_splits_exports() {
+    let mut generator = ZodGenerator::new();
+    generator.add::<Profile>().unwrap();
+    let output = generator.generate();
+    assert!(output.contains(
+        "export const SettingsInputSchema = z.object({\n  theme: z.string().default(\"\"),"
+    ));
+    assert!(output.contains("export type SettingsOutput = z.infer<typeof SettingsOutputSchema>;"));
+    assert!(output.contains("export const ProfileInputSchema = "));
+    assert!(output.contains("export const ProfileOutputSchema = "));
+    // Types that look the same on both sides keep a single export
+    assert!(output.contains("export const PointSchema = "));
+    assert!(!output.contains("PointInput"));
+    assert!(!output.contains("export const SettingsSchema"));
+
+    let declarations = generator.generate_declarations();
+    assert!(declarations.contains(
+        "export interface ProfileInput {\n  settings: SettingsInput;\n  origin: Point;\n  history: SettingsInput[];\n}"
+    ));
+    assert!(declarations.contains(
+        "export interface ProfileOutput {\n  settings: SettingsOutput;\n  origin: Point;\n  history: SettingsOutput[];\n}"
+    ));
+
+    let json = generator.generate_json_schema();
+    assert_eq!(
+        json["$defs"]["ProfileInput"]["properties"]["settings"]["$ref"],
+        "#/$defs/SettingsInput"
+    );
+    assert_eq!(
+        json["$defs"]["ProfileOutput"]["properties"]["origin"]["$ref"],
+        "#/$defs/Point"
+    );
+}
+
+#[test]
+fn test_openapi_requests_use_input_schemas() {
+    let mut generator = ZodGenerator::new();
+    generator.add::<Settings>().unwrap();
+
+    let mut api = OpenApi::new("Settings", "1.0.0");
+    api.operation(
+        Operation::put("/settings")
+            .request::<Settings>()
+            .response::<Settings>(200, "The stored settings"),
+    );
+    let output = api.to_json(&generator);
+
+    let put = &output["paths"]["/settings"]["put"];
+    assert_eq!(
+        put["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/SettingsInput",
+        "output: {output}"
+    );
+    assert_eq!(
+        put["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/SettingsOutput",
+        "output: {output}"
+    );
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Contact {
+    #[serde(alias = "mail", alias = "e_mail")]
+    email: String,
+    #[serde(alias = "tel")]
+    phone: Option<String>,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Level {
+    #[serde(alias = "lo")]
+    Low,
+    High,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum Change {
+    #[serde(alias = "rename")]
+    Rename {
+        #[serde(alias = "new_name")]
+        to: String,
+    },
+    Delete,
+}
+
+#[test]
+fn test_field_aliases() {
+    assert_eq!(
+        Contact::zod_schema(),
+        "z.object({\n  email: z.string(),\n  phone: z.string().nullable()\n})"
+    );
+    // The aliases are renamed to the field names before parsing
+    let input = Contact::zod_input_schema();
+    assert!(
+        input.starts_with("z.preprocess((value) => {\n"),
+        "input: {input}"
+    );
+    assert!(
+        input.contains(
+            "const aliases = new Map([[\"mail\", \"email\"], [\"e_mail\", \"email\"], [\"tel\", \"phone\"]]);"
+        ),
+        "input: {input}"
+    );
+    assert!(
+        input.ends_with(
+            "}, z.object({\n  email: z.string(),\n  phone: z.string().nullable().optional()\n}))"
+        ),
+        "input: {input}"
+    );
+
+    let mut generator = ZodGenerator::new();
+    generator.add::<Contact>().unwrap();
+    let declarations = generator.generate_declarations();
+    assert!(declarations.contains("export interface ContactInput {\n  email: string;\n"));
+    assert!(declarations.contains("export interface ContactOutput {"));
+}
+
+#[test]
+fn test_variant_aliases() {
+    assert_eq!(Level::zod_schema(), "z.enum(['Low', 'High'])");
+    assert_eq!(Level::zod_input_schema(), "z.enum(['Low', 'lo', 'High'])");
+
+    // Field aliases are looked up by tag, so the union stays discriminated
+    let input = Change::zod_input_schema();
+    assert!(
+        input.contains(
+            "const aliases = new Map<string, Map<string, string>>([[\"Rename\", new Map([[\"new_name\", \"to\"]])], [\"rename\", new Map([[\"new_name\", \"to\"]])]]).get((value as Record<string, unknown>)[\"type\"] as string);"
+        ),
+        "input: {input}"
+    );
+    assert!(
+        input.ends_with(
+            "}, z.discriminatedUnion('type', [z.object({\n  type: z.enum(['Rename', 'rename']),\n  to: z.string()\n}), z.object({\n  type: z.literal('Delete')\n})]))"
+        ),
+        "input: {input}"
+    );
+    assert_eq!(
+        Change::zod_schema(),
+        "z.discriminatedUnion('type', [z.object({\n  type: z.literal('Rename'),\n  to: z.string()\n}), z.object({\n  type: z.literal('Delete')\n})])"
+    );
+}
+
+#[test]
+fn test_enum_constants_skip_aliases() {
+    let mut generator = ZodGenerator::new();
+    generator.enum_constants(true).add::<Level>().unwrap();
+    let output = generator.generate();
+    assert!(output.contains("export const LevelInputSchema = z.enum(['Low', 'lo', 'High']);"));
+    assert!(
+        output.contains("export const LevelInput = {\n  Low: 'Low',\n  High: 'High',\n} as const;")
+    );
+}
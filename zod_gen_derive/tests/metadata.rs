@@ -0,0 +1,120 @@
+//! Tests for the `.meta({...})` titles, examples and ids of schemas.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use zod_gen::{ModuleOptions, ZodGenerator, ZodSchema};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[zod(title = "Money")]
+#[zod(example = json!({ "amount": 5, "currency": "EUR" }))]
+#[allow(dead_code)]
+struct Money {
+    amount: u64,
+    #[zod(title = "ISO 4217 code", example = "EUR", example = "USD")]
+    currency: String,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Invoice {
+    #[zod(example = 42)]
+    #[serde(default)]
+    number: u32,
+    #[zod(title = "Note")]
+    note: Option<String>,
+    total: Money,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[zod(title = "Status")]
+#[allow(dead_code)]
+enum Status {
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "paid")]
+    Paid,
+}
+
+#[test]
+fn test_field_metadata() {
+    assert_eq!(
+        Money::zod_schema(),
+        "z.object({\n  amount: z.number(),\n  currency: z.string().meta({\"examples\":[\"EUR\",\"USD\"],\"title\":\"ISO 4217 code\"})\n}).meta({\"examples\":[{\"amount\":5,\"currency\":\"EUR\"}],\"title\":\"Money\"})"
+    );
+}
+
+#[test]
+fn test_metadata_inside_optional_and_default() {
+    let schema = Invoice::zod_input_schema();
+    assert!(schema.contains("number: z.number().meta({\"examples\":[42]}).default(0)"));
+    assert!(schema.contains("note: z.string().nullable().meta({\"title\":\"Note\"})"));
+}
+
+#[test]
+fn test_metadata_is_not_in_typescript() {
+    let mut generator = ZodGenerator::new();
+    generator.add::<Invoice>().unwrap().add::<Status>().unwrap();
+    let declarations = generator.generate_declarations();
+    assert!(declarations
+        .contains("export interface Money {\n  amount: number;\n  currency: string;\n}"));
+    assert!(declarations.contains("export type Status = 'open' | 'paid';"));
+}
+
+#[test]
+fn test_metadata_in_json_schema() {
+    let mut generator = ZodGenerator::new();
+    generator.add::<Invoice>().unwrap();
+    let schema = generator.generate_json_schema();
+    let money = &schema["$defs"]["Money"];
+    assert_eq!(money["title"], "Money");
+    assert_eq!(
+        money["examples"],
+        json!([{ "amount": 5, "currency": "EUR" }])
+    );
+    assert_eq!(
+        money["properties"]["currency"]["examples"],
+        json!(["EUR", "USD"])
+    );
+}
+
+#[test]
+fn test_schema_ids() {
+    let mut generator = ZodGenerator::new();
+    generator
+        .schema_ids(true)
+        .add::<Invoice>()
+        .unwrap()
+        .add::<Status>()
+        .unwrap();
+    let output = generator.generate();
+    assert!(output.contains("}).meta({\"examples\":[{\"amount\":5,\"currency\":\"EUR\"}],\"id\":\"Money\",\"title\":\"Money\"});"));
+    assert!(output.contains("}).meta({\"id\":\"InvoiceInput\"});"));
+    assert!(output.contains("export const StatusSchema = z.enum(['open', 'paid']).meta({\"id\":\"Status\",\"title\":\"Status\"});"));
+}
+
+#[test]
+fn test_schema_ids_in_modules() {
+    let mut generator = ZodGenerator::new();
+    generator.schema_ids(true).add::<Invoice>().unwrap();
+    let files = generator.generate_modules(&ModuleOptions::new()).unwrap();
+    let module = files
+        .values()
+        .find(|f| f.contains("InvoiceInputSchema ="))
+        .unwrap();
+    assert!(
+        module.contains("  total: MoneySchema\n}).meta({\"id\":\"InvoiceInput\"});"),
+        "{module}"
+    );
+}
+
+#[test]
+fn test_schema_ids_keep_enum_constants() {
+    let mut generator = ZodGenerator::new();
+    generator
+        .schema_ids(true)
+        .enum_constants(true)
+        .add::<Status>()
+        .unwrap();
+    assert!(generator.generate().contains("export const Status = {"));
+}
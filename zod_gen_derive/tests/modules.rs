@@ -0,0 +1,167 @@
+//! Tests for splitting generated schemas into multiple TypeScript modules.
+
+use zod_gen::{ModuleOptions, RegistrationError, ZodGenerator};
+
+mod dto {
+    pub mod address {
+        use zod_gen_derive::ZodSchema;
+
+        #[derive(ZodSchema)]
+        #[allow(dead_code)]
+        pub struct Address {
+            pub city: String,
+        }
+    }
+
+    pub mod user {
+        use super::address::Address;
+        use zod_gen_derive::ZodSchema;
+
+        #[derive(ZodSchema)]
+        #[allow(dead_code)]
+        pub struct User {
+            pub name: String,
+            pub home: Address,
+            pub work: Option<Address>,
+            pub profile: Profile,
+        }
+
+        #[derive(ZodSchema)]
+        #[allow(dead_code)]
+        pub struct Profile {
+            pub bio: String,
+        }
+    }
+}
+
+fn generator() -> ZodGenerator {
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<dto::user::User>("User");
+    gen.add_schema::<dto::user::Profile>("Profile");
+    gen.add_schema::<dto::address::Address>("Address");
+    gen.add_schema::<Vec<dto::user::User>>("UserList");
+    gen
+}
+
+#[test]
+fn test_split_by_rust_module() {
+    let files = generator().generate_modules(&ModuleOptions::new()).unwrap();
+    let paths: Vec<&str> = files.keys().map(String::as_str).collect();
+    assert_eq!(
+        paths,
+        ["dto/address.ts", "dto/user.ts", "index.ts", "types.ts"]
+    );
+
+    let user = &files["dto/user.ts"];
+    assert!(
+        user.contains("import { AddressSchema } from './address';"),
+        "user: {user}"
+    );
+    assert!(user.contains("home: AddressSchema,"), "user: {user}");
+    // A missing `Option` is accepted on input only
+    assert!(
+        user.contains("work: AddressSchema.nullable().optional(),"),
+        "user: {user}"
+    );
+    assert!(
+        user.contains("work: AddressSchema.nullable(),"),
+        "user: {user}"
+    );
+    // Same-module dependencies are declared first and not imported
+    assert!(user.contains("profile: ProfileSchema\n"), "user: {user}");
+    let profile_at = user.find("export const ProfileSchema").unwrap();
+    let user_at = user.find("export const UserInputSchema").unwrap();
+    assert!(profile_at < user_at, "user: {user}");
+
+    let types = &files["types.ts"];
+    assert!(
+        types.contains("import { UserInputSchema, UserOutputSchema } from './dto/user';"),
+        "types: {types}"
+    );
+    assert!(
+        types.contains("export const UserListOutputSchema = z.array(UserOutputSchema);"),
+        "types: {types}"
+    );
+
+    assert_eq!(
+        files["index.ts"],
+        "// Automatically generated by zod_gen\n\
+         export * from './dto/address';\n\
+         export * from './dto/user';\n\
+         export * from './types';\n"
+    );
+}
+
+#[test]
+fn test_explicit_modules_and_esm_imports() {
+    let mut gen = generator();
+    gen.set_module("User", "users")
+        .set_module("Profile", "users")
+        .set_module("UserList", "users")
+        .set_module("Address", "shared/geo");
+    let files = gen
+        .generate_modules(&ModuleOptions::new().esm_imports(true))
+        .unwrap();
+    let paths: Vec<&str> = files.keys().map(String::as_str).collect();
+    assert_eq!(paths, ["index.ts", "shared/geo.ts", "users.ts"]);
+
+    let users = &files["users.ts"];
+    assert!(
+        users.contains("import { AddressSchema } from './shared/geo.js';"),
+        "users: {users}"
+    );
+    assert!(files["index.ts"].contains("export * from './users.js';"));
+}
+
+#[test]
+fn test_import_cycles_are_lazy() {
+    let mut gen = generator();
+    // "a" references `Address` in "b", and "b" references `User` in "a"
+    gen.set_module("User", "a")
+        .set_module("Profile", "a")
+        .set_module("Address", "b")
+        .set_module("UserList", "b");
+    let files = gen.generate_modules(&ModuleOptions::new()).unwrap();
+
+    let a = &files["a.ts"];
+    assert!(a.contains("home: z.lazy(() => AddressSchema),"), "a: {a}");
+    let b = &files["b.ts"];
+    assert!(
+        b.contains("z.array(z.lazy(() => UserOutputSchema))"),
+        "b: {b}"
+    );
+}
+
+#[test]
+fn test_index_module_is_reserved() {
+    let mut gen = generator();
+    gen.set_module("Profile", "index");
+    assert_eq!(
+        gen.generate_modules(&ModuleOptions::new()).unwrap_err(),
+        RegistrationError::ReservedModule {
+            name: "Profile".into()
+        }
+    );
+
+    // The Rust module path or the default module can collide too
+    let gen = generator();
+    assert!(gen
+        .generate_modules(&ModuleOptions::new().default_module("index"))
+        .unwrap_err()
+        .to_string()
+        .contains("`UserListInput` would be generated into the `index` module"));
+}
+
+#[test]
+fn test_write_modules_to() {
+    let dir = std::env::temp_dir().join(format!("zod_gen_modules_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let gen = generator();
+    let options = ModuleOptions::new();
+    assert_eq!(gen.write_modules_to(&dir, &options).unwrap(), 4);
+    assert_eq!(gen.write_modules_to(&dir, &options).unwrap(), 0);
+    assert!(dir.join("dto").join("user.ts").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,101 @@
+//! Tests for export names inferred from the Rust type name.
+
+use zod_gen::{RegistrationError, ZodGenerator, ZodSchema};
+
+mod billing {
+    use zod_gen_derive::ZodSchema;
+
+    #[derive(ZodSchema)]
+    #[allow(dead_code)]
+    pub struct Address {
+        pub iban: String,
+    }
+
+    #[derive(ZodSchema)]
+    #[zod(rename = "BillingAddress")]
+    #[allow(dead_code)]
+    pub struct RenamedAddress {
+        pub iban: String,
+    }
+}
+
+mod shipping {
+    use zod_gen_derive::ZodSchema;
+
+    #[derive(ZodSchema)]
+    #[allow(dead_code)]
+    pub struct Address {
+        pub street: String,
+    }
+
+    #[derive(ZodSchema)]
+    #[allow(dead_code)]
+    pub struct Order {
+        pub billing: super::billing::Address,
+        pub shipping: Address,
+    }
+}
+
+#[test]
+fn test_add_infers_name() {
+    let mut gen = ZodGenerator::new();
+    gen.add::<shipping::Address>()
+        .unwrap()
+        .add::<billing::RenamedAddress>()
+        .unwrap();
+    let output = gen.generate();
+    assert!(
+        output.contains("export const AddressSchema = z.object({\n  street: z.string()\n});"),
+        "output: {output}"
+    );
+    assert!(
+        output.contains("export const BillingAddressSchema = z.object({\n  iban: z.string()\n});"),
+        "output: {output}"
+    );
+    assert_eq!(
+        billing::RenamedAddress::type_name().as_deref(),
+        Some("BillingAddress")
+    );
+}
+
+#[test]
+fn test_duplicate_type_names_are_reported() {
+    let mut gen = ZodGenerator::new();
+    gen.add::<shipping::Address>().unwrap();
+    let err = gen.add::<billing::Address>().map(|_| ()).unwrap_err();
+    assert_eq!(
+        err,
+        RegistrationError::DuplicateName {
+            name: "Address".into(),
+            existing: "`naming::shipping::Address`".into(),
+            new: "`naming::billing::Address`".into(),
+        }
+    );
+    assert!(
+        err.to_string().contains("#[zod(rename = \"...\")]"),
+        "error: {err}"
+    );
+}
+
+#[test]
+fn test_duplicate_dependency_names_are_reported() {
+    // Both addresses of `Order` would be registered as `Address`
+    let mut gen = ZodGenerator::new();
+    let err = gen.add::<shipping::Order>().map(|_| ()).unwrap_err();
+    assert!(
+        matches!(&err, RegistrationError::DuplicateName { name, .. } if name == "Address"),
+        "error: {err}"
+    );
+    assert_eq!(gen.generate(), ZodGenerator::new().generate());
+
+    // Naming one of them explicitly resolves the conflict
+    gen.try_add_schema::<billing::Address>("BillingAddress")
+        .unwrap()
+        .add::<shipping::Order>()
+        .unwrap();
+    let declarations = gen.generate_declarations();
+    assert!(
+        declarations.contains("  billing: BillingAddress;\n  shipping: Address;"),
+        "declarations: {declarations}"
+    );
+}
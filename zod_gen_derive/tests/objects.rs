@@ -0,0 +1,188 @@
+//! Tests for object-shaped schemas: `ZodObjectSchema`, `#[serde(flatten)]`,
+//! struct tags and the `.extend`/`.pick` helpers.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use zod_gen::{ZodField, ZodGenerator, ZodObjectSchema, ZodSchema, ZodType};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize)]
+#[allow(dead_code)]
+struct Timestamps {
+    created_at: String,
+    #[serde(rename = "updatedAt")]
+    updated_at: Option<String>,
+}
+
+#[derive(ZodSchema, Serialize)]
+#[allow(dead_code)]
+struct Post {
+    id: u32,
+    #[serde(flatten)]
+    timestamps: Timestamps,
+    title: String,
+}
+
+#[derive(ZodSchema, Serialize)]
+#[allow(dead_code)]
+struct Labels {
+    name: String,
+    #[serde(flatten)]
+    extra: HashMap<String, u32>,
+}
+
+#[derive(ZodSchema, Serialize)]
+#[serde(tag = "kind")]
+#[allow(dead_code)]
+enum Event {
+    Published(Post),
+    Counters(HashMap<String, u32>),
+    Renamed {
+        #[serde(flatten)]
+        timestamps: Timestamps,
+        to: String,
+    },
+}
+
+#[derive(ZodSchema, Serialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+struct Circle {
+    radius: f64,
+}
+
+#[derive(ZodSchema, Serialize)]
+#[serde(tag = "type", rename = "square")]
+#[allow(dead_code)]
+struct Square {
+    side: f64,
+}
+
+#[derive(ZodSchema, Serialize)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum Shape {
+    Circle(Circle),
+    Square(Square),
+}
+
+/// Code that needs an object shape can require the trait.
+fn field_names<T: ZodObjectSchema>() -> Vec<String> {
+    T::object_fields().into_iter().map(|f| f.name).collect()
+}
+
+#[test]
+fn test_object_fields_accessor() {
+    assert_eq!(field_names::<Timestamps>(), ["created_at", "updatedAt"]);
+    assert_eq!(Timestamps::catchall(), None);
+    assert!(field_names::<HashMap<String, u32>>().is_empty());
+    assert_eq!(
+        <HashMap<String, u32> as ZodObjectSchema>::catchall(),
+        Some(ZodType::Number)
+    );
+}
+
+#[test]
+fn test_flatten_struct() {
+    assert_eq!(
+        field_names::<Post>(),
+        ["id", "created_at", "updatedAt", "title"]
+    );
+    assert_eq!(
+        Post::zod_schema(),
+        "z.object({\n  id: z.number(),\n  created_at: z.string(),\n  updatedAt: z.string().nullable(),\n  title: z.string()\n})"
+    );
+}
+
+#[test]
+fn test_flatten_map_is_catchall() {
+    assert_eq!(
+        Labels::zod_schema(),
+        "z.object({\n  name: z.string()\n}).catchall(z.number())"
+    );
+
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<Labels>("Labels");
+    assert!(
+        gen.generate_declarations()
+            .contains("export type Labels = { name: string } & Record<string, number>;"),
+        "declarations: {}",
+        gen.generate_declarations()
+    );
+    let json = gen.generate_json_schema();
+    assert_eq!(
+        json["$defs"]["Labels"]["additionalProperties"]["type"], "number",
+        "json: {json}"
+    );
+    assert_eq!(
+        json["$defs"]["Labels"]["required"][0], "name",
+        "json: {json}"
+    );
+}
+
+#[test]
+fn test_internally_tagged_object_payloads() {
+    let ZodType::DiscriminatedUnion { variants, .. } = Event::zod_type().unnamed().clone() else {
+        panic!("expected a discriminated union");
+    };
+    let tagged = |variant: &str| ZodField::new("kind", ZodType::literal(variant));
+
+    let mut published = vec![tagged("Published")];
+    published.extend(Post::object_fields());
+    assert_eq!(variants[0], ZodType::object(published));
+
+    assert_eq!(
+        variants[1],
+        ZodType::catchall(ZodType::object(vec![tagged("Counters")]), ZodType::Number)
+    );
+
+    let mut renamed = vec![tagged("Renamed")];
+    renamed.extend(Timestamps::object_fields());
+    renamed.push(ZodField::new("to", ZodType::String));
+    assert_eq!(variants[2], ZodType::object(renamed));
+}
+
+#[test]
+fn test_struct_tag() {
+    assert_eq!(
+        Circle::zod_schema(),
+        "z.object({\n  type: z.literal('Circle'),\n  radius: z.number()\n})"
+    );
+    assert_eq!(field_names::<Square>(), ["type", "side"]);
+    assert_eq!(
+        Shape::zod_schema(),
+        "z.union([z.object({\n  type: z.literal('Circle'),\n  radius: z.number()\n}), z.object({\n  type: z.literal('square'),\n  side: z.number()\n})])"
+    );
+    // Serde ignores the tag when deserializing
+    assert_eq!(
+        Circle::zod_input_schema(),
+        "z.object({\n  radius: z.number()\n})"
+    );
+}
+
+#[test]
+fn test_extend_and_pick() {
+    assert_eq!(
+        Post::pick(&["title", "id"]).to_zod(),
+        "z.object({\n  id: z.number(),\n  title: z.string()\n})"
+    );
+    assert_eq!(
+        Timestamps::extend(vec![
+            ZodField::new("updatedAt", ZodType::String),
+            ZodField::new("deleted", ZodType::Boolean),
+        ])
+        .to_zod(),
+        "z.object({\n  created_at: z.string(),\n  updatedAt: z.string(),\n  deleted: z.boolean()\n})"
+    );
+    assert_eq!(
+        Labels::extend(vec![ZodField::new("color", ZodType::String)]).to_zod(),
+        "z.object({\n  name: z.string(),\n  color: z.string()\n}).catchall(z.number())"
+    );
+}
+
+#[test]
+#[should_panic(expected = "has no field `name`")]
+fn test_pick_unknown_field_panics() {
+    Post::pick(&["name"]);
+}
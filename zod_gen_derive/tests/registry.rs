@@ -0,0 +1,55 @@
+//! Tests for automatic registration with `#[zod(export)]`.
+
+use zod_gen::ZodGenerator;
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema)]
+#[zod(export)]
+#[allow(dead_code)]
+struct ExportedUser {
+    name: String,
+    address: ExportedAddress,
+}
+
+mod nested {
+    use zod_gen_derive::ZodSchema;
+
+    #[derive(ZodSchema)]
+    #[zod(export)]
+    #[allow(dead_code)]
+    pub struct ExportedAddress {
+        pub city: String,
+    }
+}
+
+use nested::ExportedAddress;
+
+#[derive(ZodSchema)]
+#[allow(dead_code)]
+struct NotExported {
+    id: u32,
+}
+
+#[test]
+fn test_from_registry_collects_exported_types() {
+    let gen = ZodGenerator::from_registry();
+    let output = gen.generate();
+    assert!(
+        output.contains("export const ExportedUserSchema = z.object({"),
+        "output: {output}"
+    );
+    assert!(
+        output.contains("export const ExportedAddressSchema = z.object({"),
+        "output: {output}"
+    );
+    assert!(!output.contains("NotExported"), "output: {output}");
+}
+
+#[test]
+fn test_registry_entries_reference_each_other() {
+    let declarations = ZodGenerator::from_registry().generate_declarations();
+    assert!(
+        declarations.contains("  address: ExportedAddress;"),
+        "declarations: {declarations}"
+    );
+}
@@ -0,0 +1,153 @@
+//! Tests for `#[serde(rename_all)]` and `#[serde(rename_all_fields)]`.
+
+use serde::{Deserialize, Serialize};
+use zod_gen::testing::assert_conforms;
+use zod_gen::{ZodGenerator, ZodSchema};
+use zod_gen_derive::ZodSchema;
+
+/// A struct and an enum renamed by `$rule`, with the names serde produces
+/// and the field's key in the Zod schema.
+macro_rules! renamed {
+    ($module:ident, $rule:literal, $field:literal as $key:literal, $variant:literal) => {
+        mod $module {
+            use super::*;
+
+            #[derive(ZodSchema, Serialize, Deserialize)]
+            #[serde(rename_all = $rule)]
+            pub struct Fields {
+                pub user_id_2: u32,
+            }
+
+            #[derive(ZodSchema, Serialize, Deserialize)]
+            #[serde(rename_all = $rule)]
+            pub enum Variants {
+                HttpRequest,
+            }
+
+            #[test]
+            fn test_names_match_serde() {
+                let fields = Fields { user_id_2: 1 };
+                assert_eq!(
+                    serde_json::to_value(&fields).unwrap(),
+                    serde_json::json!({ $field: 1 })
+                );
+                assert_eq!(
+                    Fields::zod_schema(),
+                    format!("z.object({{\n  {}: z.number()\n}})", $key)
+                );
+                assert_conforms::<Fields>(&fields);
+
+                assert_eq!(
+                    serde_json::to_value(Variants::HttpRequest).unwrap(),
+                    serde_json::json!($variant)
+                );
+                assert_eq!(Variants::zod_schema(), format!("z.enum(['{}'])", $variant));
+                assert_conforms::<Variants>(&Variants::HttpRequest);
+            }
+        }
+    };
+}
+
+renamed!(lower, "lowercase", "user_id_2" as "user_id_2", "httprequest");
+renamed!(upper, "UPPERCASE", "USER_ID_2" as "USER_ID_2", "HTTPREQUEST");
+renamed!(pascal, "PascalCase", "UserId2" as "UserId2", "HttpRequest");
+renamed!(camel, "camelCase", "userId2" as "userId2", "httpRequest");
+renamed!(snake, "snake_case", "user_id_2" as "user_id_2", "http_request");
+renamed!(screaming_snake, "SCREAMING_SNAKE_CASE", "USER_ID_2" as "USER_ID_2", "HTTP_REQUEST");
+renamed!(kebab, "kebab-case", "user-id-2" as "\"user-id-2\"", "http-request");
+renamed!(screaming_kebab, "SCREAMING-KEBAB-CASE", "USER-ID-2" as "\"USER-ID-2\"", "HTTP-REQUEST");
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(rename_all(serialize = "camelCase", deserialize = "snake_case"))]
+#[allow(dead_code)]
+struct Profile {
+    display_name: String,
+    #[serde(rename = "mail")]
+    email_address: String,
+    #[serde(rename(serialize = "homePage"))]
+    home_page: String,
+}
+
+#[test]
+fn test_rename_all_per_side() {
+    assert_eq!(
+        Profile::zod_schema(),
+        "z.object({\n  displayName: z.string(),\n  mail: z.string(),\n  homePage: z.string()\n})"
+    );
+    // Explicit renames win over the rule, on the side they rename
+    assert_eq!(
+        Profile::zod_input_schema(),
+        "z.object({\n  display_name: z.string(),\n  mail: z.string(),\n  home_page: z.string()\n})"
+    );
+
+    let mut gen = ZodGenerator::new();
+    gen.add_schema::<Profile>("Profile");
+    let output = gen.generate();
+    assert!(
+        output.contains("export const ProfileInputSchema"),
+        "{output}"
+    );
+    assert!(
+        output.contains("export const ProfileOutputSchema"),
+        "{output}"
+    );
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+#[allow(dead_code)]
+enum Event {
+    PageView {
+        page_url: String,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    ButtonClick {
+        button_id: u32,
+    },
+    #[serde(rename = "signup")]
+    UserSignup {
+        #[serde(rename = "plan")]
+        plan_name: String,
+        referral_code: Option<String>,
+    },
+}
+
+#[test]
+fn test_rename_all_fields() {
+    let schema = Event::zod_schema();
+    assert!(
+        schema.contains("type: z.literal('page_view'),\n  pageUrl: z.string()"),
+        "schema: {schema}"
+    );
+    // A variant's own rule takes precedence
+    assert!(
+        schema.contains("type: z.literal('button_click'),\n  BUTTON_ID: z.number()"),
+        "schema: {schema}"
+    );
+    assert!(
+        schema.contains(
+            "type: z.literal('signup'),\n  plan: z.string(),\n  referralCode: z.string().nullable()"
+        ),
+        "schema: {schema}"
+    );
+
+    for event in [
+        Event::PageView {
+            page_url: "/".to_string(),
+        },
+        Event::ButtonClick { button_id: 7 },
+        Event::UserSignup {
+            plan_name: "pro".to_string(),
+            referral_code: None,
+        },
+    ] {
+        assert_conforms::<Event>(&event);
+    }
+    let payload = serde_json::json!({ "type": "button_click", "BUTTON_ID": 7 });
+    assert!(Event::zod_input_type().validate(&payload).is_ok());
+    assert!(serde_json::from_value::<Event>(payload).is_ok());
+}
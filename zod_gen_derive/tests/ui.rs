@@ -1,7 +1,7 @@
 //! Compile-fail tests for zod_gen_derive using trybuild.
 //!
-//! These tests verify that certain invalid enum configurations produce
-//! compile-time errors, ensuring strict alignment with Serde's rules.
+//! These tests verify that invalid or unsupported configurations produce
+//! spanned compile-time errors, ensuring strict alignment with Serde's rules.
 
 use std::path::PathBuf;
 use trybuild::TestCases;
@@ -27,3 +27,69 @@ fn test_tag_with_untagged_fails() {
     let t = TestCases::new();
     t.compile_fail(ui_test_dir().join("tag_with_untagged.rs"));
 }
+
+#[test]
+fn test_unknown_zod_attribute_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("unknown_zod_attribute.rs"));
+}
+
+#[test]
+fn test_unsupported_serde_attributes_fail() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("unsupported_serde_attributes.rs"));
+}
+
+#[test]
+fn test_invalid_rename_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("invalid_rename.rs"));
+}
+
+#[test]
+fn test_tuple_struct_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("tuple_struct.rs"));
+}
+
+#[test]
+fn test_missing_field_impl_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("missing_field_impl.rs"));
+}
+
+#[test]
+fn test_serde_with_without_override_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("serde_with_without_override.rs"));
+}
+
+#[test]
+fn test_internal_tag_string_newtype_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("internal_tag_string_newtype.rs"));
+}
+
+#[test]
+fn test_invalid_repr_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("repr_invalid.rs"));
+}
+
+#[test]
+fn test_invalid_variant_attributes_fail() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("invalid_variant_attributes.rs"));
+}
+
+#[test]
+fn test_invalid_transparent_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("transparent_invalid.rs"));
+}
+
+#[test]
+fn test_zod_default_without_serde_default_fails() {
+    let t = TestCases::new();
+    t.compile_fail(ui_test_dir().join("zod_default_without_serde_default.rs"));
+}
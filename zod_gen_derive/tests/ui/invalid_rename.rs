@@ -0,0 +1,13 @@
+// This file should NOT compile.
+// Rename values must be string literals.
+
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema)]
+#[zod(rename = Renamed)]
+#[allow(dead_code)]
+struct BadZodRename {
+    id: u32,
+}
+
+fn main() {}
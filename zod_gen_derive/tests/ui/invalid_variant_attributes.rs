@@ -0,0 +1,44 @@
+// This file should NOT compile.
+// Untagged variants must come last and `#[serde(other)]` must be on the last
+// unit variant of a tagged enum, as serde requires. The errors match serde's,
+// so each is reported once.
+
+use serde::{Deserialize, Serialize};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Misplaced {
+    #[serde(untagged)]
+    Raw(String),
+    Tagged(u32),
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum OtherWithPayload {
+    Known,
+    #[serde(other)]
+    Unknown(String),
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum OtherNotLast {
+    #[serde(other)]
+    Unknown,
+    Known,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum OtherInUntagged {
+    Value(u32),
+    #[serde(other)]
+    Unknown,
+}
+
+fn main() {}
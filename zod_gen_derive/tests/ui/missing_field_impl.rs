@@ -0,0 +1,16 @@
+// This file should NOT compile.
+// A field type without a ZodSchema impl is reported at the field's type.
+
+use zod_gen_derive::ZodSchema;
+
+struct Timestamp(u64);
+
+#[derive(ZodSchema)]
+#[allow(dead_code)]
+struct Event {
+    name: String,
+    at: Timestamp,
+    history: Vec<Timestamp>,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+// This file should NOT compile.
+// `#[zod(repr)]` describes enums serialized as their discriminant, which
+// only exists for unit variants.
+
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema)]
+#[zod(repr)]
+#[allow(dead_code)]
+enum Code {
+    Ok,
+    Custom(u16),
+}
+
+#[derive(ZodSchema)]
+#[zod(repr)]
+#[allow(dead_code)]
+struct Wrapper {
+    code: u16,
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+// This file should NOT compile.
+// Custom (de)serialization functions need a #[zod(as = ...)] schema, which
+// variants cannot have.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zod_gen_derive::ZodSchema;
+
+fn as_string<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn from_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Account {
+    #[serde(serialize_with = "as_string")]
+    balance: u64,
+    #[serde(deserialize_with = "from_string")]
+    limit: u64,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+enum Payment {
+    #[serde(deserialize_with = "from_string")]
+    Amount(u64),
+}
+
+fn main() {}
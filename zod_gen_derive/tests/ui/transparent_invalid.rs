@@ -0,0 +1,36 @@
+// This file should NOT compile.
+// `#[serde(transparent)]` needs exactly one field to serialize as, and
+// is not allowed on enums or unit structs.
+
+use serde::{Deserialize, Serialize};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[allow(dead_code)]
+struct TwoFields {
+    id: u64,
+    name: String,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[allow(dead_code)]
+struct AllSkipped {
+    #[serde(skip)]
+    id: u64,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[allow(dead_code)]
+struct Unit;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+#[allow(dead_code)]
+enum Choice {
+    A(u64),
+}
+
+fn main() {}
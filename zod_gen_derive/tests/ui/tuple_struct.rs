@@ -0,0 +1,10 @@
+// This file should NOT compile.
+// Only structs with named fields and newtype structs are supported.
+
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema)]
+#[allow(dead_code)]
+struct Pair(u32, String);
+
+fn main() {}
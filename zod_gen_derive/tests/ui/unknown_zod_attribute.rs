@@ -0,0 +1,13 @@
+// This file should NOT compile.
+// Unknown #[zod(...)] options are rejected instead of silently ignored.
+
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema)]
+#[zod(exprot)]
+#[allow(dead_code)]
+struct Typo {
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// This file should NOT compile.
+// Unknown `rename_all` rules and `rename_all_fields` on a struct are
+// reported at the attribute with serde's messages, all at once.
+
+use serde::{Deserialize, Serialize};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(rename_all = "Title Case", deny_unknown_fields)]
+#[allow(dead_code)]
+struct UnknownRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+#[allow(dead_code)]
+struct StructFields {
+    first_name: String,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+#[allow(dead_code)]
+enum UnknownVariantRule {
+    #[serde(rename_all(serialize = "UPPERCASE", deserialize = "kebab"))]
+    Hidden { hidden_reason: String },
+    #[serde(rename = "shown", alias = "visible")]
+    Shown,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+// This file should NOT compile.
+// `#[zod(default = ...)]` describes the value serde fills in, so the field
+// has to be `#[serde(default)]`.
+
+use serde::Deserialize;
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Deserialize)]
+#[allow(dead_code)]
+struct Settings {
+    #[zod(default = "dark")]
+    theme: String,
+}
+
+fn main() {}
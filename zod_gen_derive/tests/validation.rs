@@ -0,0 +1,106 @@
+//! Tests that serialized values pass the derived schemas' validation, and
+//! that JSON serde rejects is reported like Zod would.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use zod_gen::{PathSegment, ZodSchema};
+use zod_gen_derive::ZodSchema;
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Order {
+    id: u32,
+    #[serde(rename = "customerName")]
+    customer_name: String,
+    note: Option<String>,
+    #[serde(default, alias = "lines")]
+    items: Vec<Item>,
+    status: Status,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct Item {
+    sku: String,
+    quantity: u16,
+}
+
+#[derive(ZodSchema, Serialize, Deserialize)]
+#[serde(tag = "state", content = "detail")]
+#[allow(dead_code)]
+enum Status {
+    Open,
+    Shipped(String),
+    Cancelled { reason: String },
+}
+
+fn order() -> Order {
+    Order {
+        id: 7,
+        customer_name: "Ada".to_string(),
+        note: None,
+        items: vec![Item {
+            sku: "A-1".to_string(),
+            quantity: 2,
+        }],
+        status: Status::Cancelled {
+            reason: "duplicate".to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_serialized_values_pass() {
+    let value = serde_json::to_value(order()).unwrap();
+    assert_eq!(Order::zod_type().validate(&value), Ok(()));
+
+    for status in [Status::Open, Status::Shipped("DHL".to_string())] {
+        let value = serde_json::to_value(status).unwrap();
+        assert_eq!(Status::zod_type().validate(&value), Ok(()));
+    }
+}
+
+#[test]
+fn test_input_rules() {
+    let input = json!({
+        "id": 7,
+        "customerName": "Ada",
+        "note": null,
+        "lines": [{ "sku": "A-1", "quantity": 2 }],
+        "status": { "state": "Open" },
+    });
+    assert!(serde_json::from_value::<Order>(input.clone()).is_ok());
+    assert_eq!(Order::zod_input_type().validate(&input), Ok(()));
+    // Aliases are only accepted when deserializing
+    assert!(Order::zod_type().validate(&input).is_err());
+}
+
+#[test]
+fn test_issues() {
+    let input = json!({
+        "id": "7",
+        "customerName": "Ada",
+        "note": null,
+        "items": [{ "sku": "A-1" }],
+        "status": { "state": "Lost" },
+    });
+    assert!(serde_json::from_value::<Order>(input.clone()).is_err());
+    let issues = Order::zod_type().validate(&input).unwrap_err();
+    let described: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        described,
+        [
+            "Invalid input: expected number, received string at id",
+            "Invalid input: expected number, received undefined at items[0].quantity",
+            "Invalid input at status.state",
+        ]
+    );
+    assert_eq!(
+        issues[1].path,
+        [
+            PathSegment::Key("items".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Key("quantity".to_string()),
+        ]
+    );
+}